@@ -0,0 +1,965 @@
+//! Bitboards, sets of squares packed into a single `u64`.
+//!
+//! Bit `n` of a [`Bitboard`] corresponds to the square with index `n`, so bit 0 is a1 and bit 63
+//! is h8.
+
+use std::error::Error;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, Shr};
+use std::sync::OnceLock;
+
+use crate::color::Color;
+use crate::fen::{self, FenError};
+use crate::square::Square;
+
+/// Every square except those on the a-file.
+const NOT_FILE_A: u64 = !Bitboard::FILES[0].bits;
+
+/// Every square except those on the h-file.
+const NOT_FILE_H: u64 = !Bitboard::FILES[7].bits;
+
+/// An error which can be returned when parsing a [`Bitboard`] grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GridError {
+    /// The grid does not have exactly eight ranks.
+    WrongRankCount,
+    /// A rank of the grid does not have exactly eight files.
+    WrongFileCount,
+    /// A cell of the grid is neither `0` nor `1`.
+    InvalidCell,
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            | GridError::WrongRankCount => write!(f, "grid does not have eight ranks"),
+            | GridError::WrongFileCount => write!(f, "grid rank does not have eight files"),
+            | GridError::InvalidCell => write!(f, "grid cell is neither 0 nor 1"),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/// A set of squares, one bit per square.
+///
+/// # Examples
+///
+/// ```
+/// use sachy::bitboard::Bitboard;
+/// use sachy::square::Square;
+///
+/// let mut bb = Bitboard::new();
+/// bb.set(Square::from_string("e4").unwrap());
+/// assert!(bb.get(Square::from_string("e4").unwrap()));
+/// assert_eq!(bb.count(), 1);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bitboard {
+    /// The raw bits of the bitboard, where bit `n` is the square with index `n`.
+    pub bits: u64,
+}
+
+impl Bitboard {
+    /// The bitboard with no squares set.
+    pub const EMPTY: Bitboard = Bitboard { bits: 0 };
+    /// The bitboard with every square set.
+    pub const FULL: Bitboard = Bitboard { bits: u64::MAX };
+    /// The masks of every rank, from rank 1 to rank 8.
+    pub const RANKS: [Bitboard; 8] = {
+        let mut ranks = [Bitboard::EMPTY; 8];
+        let mut rank = 0;
+        while rank < 8 {
+            ranks[rank] = Bitboard {
+                bits: 0xff << (rank * 8),
+            };
+            rank += 1;
+        }
+        ranks
+    };
+    /// The masks of every file, from the a-file to the h-file.
+    pub const FILES: [Bitboard; 8] = {
+        let mut files = [Bitboard::EMPTY; 8];
+        let mut file = 0;
+        while file < 8 {
+            files[file] = Bitboard {
+                bits: 0x0101_0101_0101_0101 << file,
+            };
+            file += 1;
+        }
+        files
+    };
+    /// The 32 light squares, such as h1.
+    pub const LIGHT_SQUARES: Bitboard = Bitboard {
+        bits: 0x55aa_55aa_55aa_55aa,
+    };
+    /// The 32 dark squares, such as a1.
+    pub const DARK_SQUARES: Bitboard = Bitboard {
+        bits: 0xaa55_aa55_aa55_aa55,
+    };
+
+    /// Creates a new empty bitboard.
+    pub fn new() -> Bitboard { Bitboard::EMPTY }
+
+    /// Returns the mask of the rank `r`, where 0 is rank 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r` is not in the range `0..8`.
+    pub const fn rank(r: u8) -> Bitboard { Bitboard::RANKS[r as usize] }
+
+    /// Returns the mask of the file `f`, where 0 is the a-file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` is not in the range `0..8`.
+    pub const fn file(f: u8) -> Bitboard { Bitboard::FILES[f as usize] }
+
+    /// Parses a grid in the format produced by the [`Display`](fmt::Display) implementation.
+    ///
+    /// The grid consists of eight lines of eight whitespace-separated `0`s and `1`s, with rank 8
+    /// on the first line and the a-file in the first column, so that parsing the output of
+    /// `to_string` returns the original bitboard.
+    ///
+    /// ```
+    /// use sachy::bitboard::Bitboard;
+    ///
+    /// let bb = Bitboard::from(0x8100_0000_0000_0081);
+    /// assert_eq!(Bitboard::from_grid(&bb.to_string()), Ok(bb));
+    /// ```
+    pub fn from_grid(grid: &str) -> Result<Bitboard, GridError> {
+        let mut bb = Bitboard::EMPTY;
+        let mut ranks = 0;
+
+        for (line_index, line) in grid.lines().enumerate() {
+            if line_index > 7 {
+                return Err(GridError::WrongRankCount);
+            }
+
+            let rank = 7 - line_index;
+
+            let mut files = 0;
+
+            for (file, cell) in line.split_whitespace().enumerate() {
+                if file > 7 {
+                    return Err(GridError::WrongFileCount);
+                }
+
+                match cell {
+                    | "0" => {}
+                    | "1" => bb.bits |= 1 << (rank * 8 + file),
+                    | _ => return Err(GridError::InvalidCell),
+                }
+
+                files += 1;
+            }
+
+            if files != 8 {
+                return Err(GridError::WrongFileCount);
+            }
+
+            ranks += 1;
+        }
+
+        if ranks != 8 {
+            return Err(GridError::WrongRankCount);
+        }
+
+        Ok(bb)
+    }
+
+    /// Parses a single rank field of a FEN piece placement, such as `"p1p1p1p1"`, into the squares
+    /// of rank `rank`, where 0 is the first rank. Digits from 1 to 8 stand for that many empty
+    /// squares and any other character for an occupied one.
+    ///
+    /// ```
+    /// use sachy::bitboard::Bitboard;
+    /// use sachy::fen::FenError;
+    ///
+    /// assert_eq!(Bitboard::from_fen_rank(1, "PPPPPPPP"), Ok(Bitboard::rank(1)));
+    /// assert_eq!(Bitboard::from_fen_rank(0, "8"), Ok(Bitboard::EMPTY));
+    /// assert_eq!(Bitboard::from_fen_rank(8, "8"), Err(FenError::RankOutOfBounds));
+    /// ```
+    pub fn from_fen_rank(rank: u8, field: &str) -> Result<Bitboard, FenError> {
+        if rank > 7 {
+            return Err(FenError::RankOutOfBounds);
+        }
+
+        let mut bb = Bitboard::EMPTY;
+        fen::parse_rank(field, |file, _| {
+            bb.bits |= 1 << (rank * 8 + file);
+            Ok(())
+        })?;
+
+        Ok(bb)
+    }
+
+    /// Returns whether the given square is set.
+    pub fn get(&self, sq: Square) -> bool { self.bits & (1 << sq.index()) != 0 }
+
+    /// Sets the given square.
+    pub fn set(&mut self, sq: Square) { self.bits |= 1 << sq.index(); }
+
+    /// Clears the given square.
+    pub fn clear(&mut self, sq: Square) { self.bits &= !(1 << sq.index()); }
+
+    /// Sets or clears the given square depending on `value`.
+    pub fn put(&mut self, sq: Square, value: bool) {
+        if value {
+            self.set(sq);
+        } else {
+            self.clear(sq);
+        }
+    }
+
+    /// Returns the number of set squares.
+    pub fn count(&self) -> u32 { self.bits.count_ones() }
+
+    /// Returns whether any square is set.
+    pub fn any(&self) -> bool { self.bits != 0 }
+
+    /// Returns whether no square is set.
+    pub fn none(&self) -> bool { self.bits == 0 }
+
+    /// Returns the lowest set square, or `None` if the bitboard is empty.
+    pub fn lsb(&self) -> Option<Square> {
+        if self.bits == 0 {
+            return None;
+        }
+
+        Square::from_index(self.bits.trailing_zeros() as u8).ok()
+    }
+
+    /// Returns the highest set square, or `None` if the bitboard is empty.
+    pub fn msb(&self) -> Option<Square> {
+        if self.bits == 0 {
+            return None;
+        }
+
+        Square::from_index(63 - self.bits.leading_zeros() as u8).ok()
+    }
+
+    /// Returns an iterator over every subset of the bitboard, starting with the empty set and
+    /// ending with the bitboard itself.
+    ///
+    /// ```
+    /// use sachy::bitboard::Bitboard;
+    ///
+    /// assert_eq!(Bitboard::from(0b1011).subsets().count(), 8);
+    /// ```
+    pub fn subsets(&self) -> Subsets {
+        Subsets {
+            mask: self.bits,
+            subset: Some(0),
+        }
+    }
+
+    /// Removes the lowest set square from the bitboard and returns it, or `None` if the bitboard
+    /// is empty.
+    pub fn pop_lsb(&mut self) -> Option<Square> {
+        let sq = self.lsb();
+        self.bits &= self.bits.wrapping_sub(1);
+        sq
+    }
+
+    /// Folds every set square into an accumulator, in ascending index order, starting from `init`.
+    ///
+    /// ```
+    /// use sachy::bitboard::Bitboard;
+    ///
+    /// let sum = Bitboard::from(0b1011).fold(0, |sum, sq| sum + sq.index());
+    /// assert_eq!(sum, 4);
+    /// ```
+    pub fn fold<B, F: FnMut(B, Square) -> B>(self, init: B, f: F) -> B {
+        self.into_iter().fold(init, f)
+    }
+
+    /// Returns the Chebyshev distance from `sq` to the nearest set square, or `None` if the
+    /// bitboard is empty.
+    ///
+    /// ```
+    /// use sachy::bitboard::Bitboard;
+    /// use sachy::square::Square;
+    ///
+    /// let bb = Bitboard::from(Square::from_string("h8").unwrap());
+    /// assert_eq!(bb.nearest_distance(Square::from_string("e4").unwrap()), Some(4));
+    /// ```
+    pub fn nearest_distance(&self, sq: Square) -> Option<u8> {
+        self.into_iter().map(|other| sq.distance(other)).min()
+    }
+
+    /// Returns an iterator over the set squares in ascending index order, each paired with the
+    /// bitboard containing only that square.
+    ///
+    /// ```
+    /// use sachy::bitboard::Bitboard;
+    ///
+    /// for (sq, bit) in Bitboard::from(0b1011).iter_bits() {
+    ///     assert_eq!(Bitboard::from(sq), bit);
+    /// }
+    /// ```
+    pub fn iter_bits(self) -> impl Iterator<Item = (Square, Bitboard)> {
+        self.into_iter().map(|sq| (sq, Bitboard::from(sq)))
+    }
+
+    /// Returns the bitboard flipped vertically, swapping rank 1 with rank 8, rank 2 with rank 7,
+    /// and so on.
+    pub fn flip_vertical(&self) -> Bitboard {
+        Bitboard {
+            bits: self.bits.swap_bytes(),
+        }
+    }
+
+    /// Returns the bitboard mirrored horizontally, swapping the a-file with the h-file, the b-file
+    /// with the g-file, and so on.
+    pub fn mirror_horizontal(&self) -> Bitboard {
+        Bitboard {
+            bits: self.bits.reverse_bits().swap_bytes(),
+        }
+    }
+
+    /// Returns the bitboard shifted one rank north, dropping squares on rank 8.
+    pub fn north(self) -> Bitboard {
+        Bitboard {
+            bits: self.bits << 8,
+        }
+    }
+
+    /// Returns the bitboard shifted one rank south, dropping squares on rank 1.
+    pub fn south(self) -> Bitboard {
+        Bitboard {
+            bits: self.bits >> 8,
+        }
+    }
+
+    /// Returns the bitboard shifted one file east, dropping squares on the h-file.
+    pub fn east(self) -> Bitboard {
+        Bitboard {
+            bits: (self.bits << 1) & NOT_FILE_A,
+        }
+    }
+
+    /// Returns the bitboard shifted one file west, dropping squares on the a-file.
+    pub fn west(self) -> Bitboard {
+        Bitboard {
+            bits: (self.bits >> 1) & NOT_FILE_H,
+        }
+    }
+
+    /// Returns the bitboard shifted one square north-east.
+    pub fn north_east(self) -> Bitboard {
+        Bitboard {
+            bits: (self.bits << 9) & NOT_FILE_A,
+        }
+    }
+
+    /// Returns the bitboard shifted one square north-west.
+    pub fn north_west(self) -> Bitboard {
+        Bitboard {
+            bits: (self.bits << 7) & NOT_FILE_H,
+        }
+    }
+
+    /// Returns the bitboard shifted one square south-east.
+    pub fn south_east(self) -> Bitboard {
+        Bitboard {
+            bits: (self.bits >> 7) & NOT_FILE_A,
+        }
+    }
+
+    /// Returns the bitboard shifted one square south-west.
+    pub fn south_west(self) -> Bitboard {
+        Bitboard {
+            bits: (self.bits >> 9) & NOT_FILE_H,
+        }
+    }
+
+    /// Returns the bitboard shifted one rank forward for `color`, north for white and south for
+    /// black.
+    ///
+    /// ```
+    /// use sachy::bitboard::Bitboard;
+    /// use sachy::Color;
+    ///
+    /// assert_eq!(Bitboard::rank(1).forward(Color::White), Bitboard::rank(2));
+    /// assert_eq!(Bitboard::rank(6).forward(Color::Black), Bitboard::rank(5));
+    /// ```
+    pub fn forward(self, color: Color) -> Bitboard {
+        match color {
+            | Color::White => self.north(),
+            | Color::Black => self.south(),
+        }
+    }
+
+    /// Returns the bitboard shifted one rank forward for `color` and one file towards the a-file,
+    /// dropping squares on the a-file.
+    pub fn forward_left(self, color: Color) -> Bitboard {
+        match color {
+            | Color::White => self.north_west(),
+            | Color::Black => self.south_west(),
+        }
+    }
+
+    /// Returns the bitboard shifted one rank forward for `color` and one file towards the h-file,
+    /// dropping squares on the h-file.
+    pub fn forward_right(self, color: Color) -> Bitboard {
+        match color {
+            | Color::White => self.north_east(),
+            | Color::Black => self.south_east(),
+        }
+    }
+
+    /// Renders the bitboard as a labelled grid like the alternate [`Display`](fmt::Display) form,
+    /// with squares in `highlight` drawn as `X`, other set squares as `*` and the rest as `.`.
+    ///
+    /// ```
+    /// use sachy::bitboard::Bitboard;
+    ///
+    /// let pretty = Bitboard::from(0b11).pretty_with(Bitboard::from(0b110));
+    /// let mut lines = pretty.lines().rev();
+    /// assert_eq!(lines.next(), Some("  a b c d e f g h"));
+    /// assert_eq!(lines.next(), Some("1 * X X . . . . ."));
+    /// ```
+    pub fn pretty_with(&self, highlight: Bitboard) -> String {
+        let mut out = String::new();
+        write_grid(&mut out, true, |sq| {
+            if highlight.get(sq) {
+                'X'
+            } else if self.get(sq) {
+                '*'
+            } else {
+                '.'
+            }
+        })
+        .expect("writing to a String cannot fail");
+        out
+    }
+}
+
+impl Default for Bitboard {
+    fn default() -> Bitboard { Bitboard::new() }
+}
+
+impl From<u64> for Bitboard {
+    fn from(bits: u64) -> Bitboard { Bitboard { bits } }
+}
+
+impl From<Square> for Bitboard {
+    fn from(sq: Square) -> Bitboard {
+        Bitboard {
+            bits: 1 << sq.index(),
+        }
+    }
+}
+
+impl From<Bitboard> for u64 {
+    fn from(bb: Bitboard) -> u64 { bb.bits }
+}
+
+/// An iterator over the set squares of a [`Bitboard`], in ascending index order.
+///
+/// Created by the [`IntoIterator`] implementations of [`Bitboard`].
+#[derive(Debug, Clone)]
+pub struct SquareIter {
+    bb: Bitboard,
+}
+
+impl Iterator for SquareIter {
+    type Item = Square;
+
+    fn next(&mut self) -> Option<Square> { self.bb.pop_lsb() }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bb.count() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for SquareIter {}
+
+impl FusedIterator for SquareIter {}
+
+impl IntoIterator for Bitboard {
+    type IntoIter = SquareIter;
+    type Item = Square;
+
+    fn into_iter(self) -> SquareIter { SquareIter { bb: self } }
+}
+
+impl IntoIterator for &Bitboard {
+    type IntoIter = SquareIter;
+    type Item = Square;
+
+    fn into_iter(self) -> SquareIter { SquareIter { bb: *self } }
+}
+
+/// An iterator over every subset of a [`Bitboard`], using the carry-rippler trick.
+///
+/// Created by [`Bitboard::subsets`].
+#[derive(Debug, Clone)]
+pub struct Subsets {
+    mask: u64,
+    subset: Option<u64>,
+}
+
+impl Iterator for Subsets {
+    type Item = Bitboard;
+
+    fn next(&mut self) -> Option<Bitboard> {
+        let subset = self.subset?;
+        let next = subset.wrapping_sub(self.mask) & self.mask;
+        self.subset = (next != 0).then_some(next);
+        Some(Bitboard { bits: subset })
+    }
+}
+
+impl FusedIterator for Subsets {}
+
+impl FromIterator<Square> for Bitboard {
+    fn from_iter<I: IntoIterator<Item = Square>>(iter: I) -> Bitboard {
+        let mut bb = Bitboard::EMPTY;
+        bb.extend(iter);
+        bb
+    }
+}
+
+impl Extend<Square> for Bitboard {
+    fn extend<I: IntoIterator<Item = Square>>(&mut self, iter: I) {
+        for sq in iter {
+            self.set(sq);
+        }
+    }
+}
+
+/// Bitboards serialize as their raw `u64` value.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Bitboard {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.bits)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Bitboard {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Bitboard, D::Error> {
+        <u64 as serde::Deserialize>::deserialize(deserializer).map(Bitboard::from)
+    }
+}
+
+/// Implements a binary bitwise operator and its assigning variant for `Bitboard`, both by value
+/// and by reference.
+macro_rules! impl_bit_op {
+    ($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident) => {
+        impl $op for Bitboard {
+            type Output = Bitboard;
+
+            fn $op_fn(self, rhs: Bitboard) -> Bitboard {
+                Bitboard {
+                    bits: self.bits.$op_fn(rhs.bits),
+                }
+            }
+        }
+
+        impl $op for &Bitboard {
+            type Output = Bitboard;
+
+            fn $op_fn(self, rhs: &Bitboard) -> Bitboard {
+                Bitboard {
+                    bits: self.bits.$op_fn(rhs.bits),
+                }
+            }
+        }
+
+        impl $assign for Bitboard {
+            fn $assign_fn(&mut self, rhs: Bitboard) { self.bits.$assign_fn(rhs.bits); }
+        }
+
+        impl $assign<&Bitboard> for Bitboard {
+            fn $assign_fn(&mut self, rhs: &Bitboard) { self.bits.$assign_fn(rhs.bits); }
+        }
+    };
+}
+
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+impl Not for Bitboard {
+    type Output = Bitboard;
+
+    fn not(self) -> Bitboard { Bitboard { bits: !self.bits } }
+}
+
+impl Not for &Bitboard {
+    type Output = Bitboard;
+
+    fn not(self) -> Bitboard { Bitboard { bits: !self.bits } }
+}
+
+/// Shifts the raw bits towards h8, without any masking of wrapped files. Shifting by 64 or more
+/// yields an empty bitboard.
+impl Shl<u32> for Bitboard {
+    type Output = Bitboard;
+
+    fn shl(self, rhs: u32) -> Bitboard {
+        Bitboard {
+            bits: self.bits.checked_shl(rhs).unwrap_or(0),
+        }
+    }
+}
+
+/// Shifts the raw bits towards a1, without any masking of wrapped files. Shifting by 64 or more
+/// yields an empty bitboard.
+impl Shr<u32> for Bitboard {
+    type Output = Bitboard;
+
+    fn shr(self, rhs: u32) -> Bitboard {
+        Bitboard {
+            bits: self.bits.checked_shr(rhs).unwrap_or(0),
+        }
+    }
+}
+
+/// Formats the bitboard as a grid of `1`s and `0`s, one rank per line, with rank 8 at the top and
+/// the a-file on the left, as the board is usually drawn.
+///
+/// ```
+/// use sachy::bitboard::Bitboard;
+///
+/// let bb = Bitboard::from(0b101);
+/// let last_line = bb.to_string().lines().last().unwrap().to_owned();
+/// assert_eq!(last_line, "1 0 1 0 0 0 0 0");
+/// ```
+///
+/// The alternate form (`{:#}`) additionally labels the board, with the rank numbers down the left
+/// side and the file letters under the grid.
+///
+/// ```
+/// use sachy::bitboard::Bitboard;
+///
+/// let bb = Bitboard::from(0b101);
+/// let labelled = format!("{:#}", bb);
+/// let mut lines = labelled.lines().rev();
+/// assert_eq!(lines.next(), Some("  a b c d e f g h"));
+/// assert_eq!(lines.next(), Some("1 1 0 1 0 0 0 0 0"));
+/// ```
+impl fmt::Display for Bitboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labelled = f.alternate();
+        write_grid(f, labelled, |sq| if self.get(sq) { '1' } else { '0' })
+    }
+}
+
+/// Writes a grid with one `glyph` per square, one rank per line, with rank 8 at the top and the
+/// a-file on the left. If `labelled` is set, the rank numbers are written down the left side and
+/// the file letters under the grid.
+fn write_grid<W: fmt::Write>(
+    out: &mut W,
+    labelled: bool,
+    glyph: impl Fn(Square) -> char,
+) -> fmt::Result {
+    for rank in (0..8).rev() {
+        if labelled {
+            write!(out, "{} ", rank + 1)?;
+        }
+
+        for file in 0..8 {
+            if file != 0 {
+                write!(out, " ")?;
+            }
+
+            write!(out, "{}", glyph(Square::ALL[rank * 8 + file]))?;
+        }
+
+        if rank != 0 {
+            writeln!(out)?;
+        }
+    }
+
+    if labelled {
+        write!(out, "\n  a b c d e f g h")?;
+    }
+
+    Ok(())
+}
+
+/// Formats the bitboard as a grid of `1`s and `0`s below a `Bitboard:` header, with rank 8 at the
+/// top and rank 1 at the bottom, as the board is usually drawn.
+///
+/// ```
+/// use sachy::bitboard::Bitboard;
+///
+/// let bb = Bitboard::from(1 << 63);
+/// let debug = format!("{:?}", bb);
+/// let mut lines = debug.lines();
+/// assert_eq!(lines.next(), Some("Bitboard:"));
+/// assert_eq!(lines.next(), Some("0 0 0 0 0 0 0 1"));
+/// ```
+impl fmt::Debug for Bitboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "Bitboard:\n{}", self) }
+}
+
+/// One of the eight compass directions on the board, with north pointing towards rank 8 and east
+/// towards the h-file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Towards rank 8.
+    North,
+    /// Towards h8.
+    NorthEast,
+    /// Towards the h-file.
+    East,
+    /// Towards h1.
+    SouthEast,
+    /// Towards rank 1.
+    South,
+    /// Towards a1.
+    SouthWest,
+    /// Towards the a-file.
+    West,
+    /// Towards a8.
+    NorthWest,
+}
+
+impl Direction {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// Returns the direction pointing the opposite way.
+    pub fn opposite(self) -> Direction { Direction::ALL[(self as usize + 4) % 8] }
+
+    /// Returns whether moving in this direction increases the square index.
+    pub fn is_increasing(self) -> bool {
+        matches!(
+            self,
+            Direction::North | Direction::NorthEast | Direction::East | Direction::NorthWest
+        )
+    }
+
+    /// Returns `bb` shifted one square in this direction, dropping squares which leave the board.
+    pub fn shift(self, bb: Bitboard) -> Bitboard {
+        match self {
+            | Direction::North => bb.north(),
+            | Direction::NorthEast => bb.north_east(),
+            | Direction::East => bb.east(),
+            | Direction::SouthEast => bb.south_east(),
+            | Direction::South => bb.south(),
+            | Direction::SouthWest => bb.south_west(),
+            | Direction::West => bb.west(),
+            | Direction::NorthWest => bb.north_west(),
+        }
+    }
+}
+
+/// The `(dx, dy)` offsets of the squares a knight attacks.
+const KNIGHT_OFFSETS: [(i8, i8); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+
+/// The `(dx, dy)` offsets of the squares a king attacks.
+const KING_OFFSETS: [(i8, i8); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+
+/// Builds a table of the squares reachable from each square by one of the given offsets,
+/// discarding targets which fall off the board.
+fn offset_table(offsets: &[(i8, i8)]) -> [Bitboard; 64] {
+    let mut table = [Bitboard::EMPTY; 64];
+
+    for sq in Square::ALL {
+        for &(dx, dy) in offsets {
+            let x = u8::try_from(sq.x() as i8 + dx);
+            let y = u8::try_from(sq.y() as i8 + dy);
+
+            if let (Ok(x), Ok(y)) = (x, y) {
+                if let Ok(target) = Square::new(x, y) {
+                    table[sq.index() as usize].set(target);
+                }
+            }
+        }
+    }
+
+    table
+}
+
+/// Returns the squares attacked by a knight on `sq`.
+///
+/// ```
+/// use sachy::bitboard;
+/// use sachy::square::Square;
+///
+/// let attacks = bitboard::knight_attacks(Square::from_string("a1").unwrap());
+/// assert_eq!(attacks.count(), 2);
+/// assert!(attacks.get(Square::from_string("b3").unwrap()));
+/// assert!(attacks.get(Square::from_string("c2").unwrap()));
+/// ```
+pub fn knight_attacks(sq: Square) -> Bitboard {
+    static TABLE: OnceLock<[Bitboard; 64]> = OnceLock::new();
+    TABLE.get_or_init(|| offset_table(&KNIGHT_OFFSETS))[sq.index() as usize]
+}
+
+/// Returns the squares attacked by a king on `sq`.
+///
+/// ```
+/// use sachy::bitboard;
+/// use sachy::square::Square;
+///
+/// let attacks = bitboard::king_attacks(Square::from_string("h8").unwrap());
+/// assert_eq!(attacks.count(), 3);
+/// ```
+pub fn king_attacks(sq: Square) -> Bitboard {
+    static TABLE: OnceLock<[Bitboard; 64]> = OnceLock::new();
+    TABLE.get_or_init(|| offset_table(&KING_OFFSETS))[sq.index() as usize]
+}
+
+/// Returns the squares attacked by a pawn of the given `color` on `sq`.
+///
+/// ```
+/// use sachy::bitboard;
+/// use sachy::square::Square;
+/// use sachy::Color;
+///
+/// let attacks = bitboard::pawn_attacks(Square::from_string("e4").unwrap(), Color::White);
+/// assert!(attacks.get(Square::from_string("d5").unwrap()));
+/// assert!(attacks.get(Square::from_string("f5").unwrap()));
+/// ```
+pub fn pawn_attacks(sq: Square, color: Color) -> Bitboard {
+    let pawn = Bitboard::from(sq);
+    pawn.forward_left(color) | pawn.forward_right(color)
+}
+
+/// Returns the squares on the ray from `sq` in the direction `dir`, excluding `sq` itself.
+///
+/// The ray runs to the edge of the board, and is empty if `sq` is already on that edge.
+///
+/// ```
+/// use sachy::bitboard::{self, Direction};
+/// use sachy::square::Square;
+///
+/// let north = bitboard::ray(Square::from_string("e4").unwrap(), Direction::North);
+/// assert_eq!(north.count(), 4);
+/// ```
+pub fn ray(sq: Square, dir: Direction) -> Bitboard {
+    static TABLE: OnceLock<[[Bitboard; 8]; 64]> = OnceLock::new();
+
+    let table = TABLE.get_or_init(|| {
+        let mut table = [[Bitboard::EMPTY; 8]; 64];
+
+        for sq in Square::ALL {
+            for dir in Direction::ALL {
+                let mut step = dir.shift(Bitboard::from(sq));
+
+                while step.any() {
+                    table[sq.index() as usize][dir as usize] |= step;
+                    step = dir.shift(step);
+                }
+            }
+        }
+
+        table
+    });
+
+    table[sq.index() as usize][dir as usize]
+}
+
+/// Returns the squares on the ray from `sq` in the direction `dir`, stopping at and including the
+/// first square in `occupied`.
+fn blocked_ray(sq: Square, dir: Direction, occupied: Bitboard) -> Bitboard {
+    let attacks = ray(sq, dir);
+    let blockers = attacks & occupied;
+    let blocker = if dir.is_increasing() {
+        blockers.lsb()
+    } else {
+        blockers.msb()
+    };
+
+    match blocker {
+        | Some(blocker) => attacks ^ ray(blocker, dir),
+        | None => attacks,
+    }
+}
+
+/// Returns the squares attacked by a rook on `sq`, given the `occupied` squares of the board.
+///
+/// Each ray stops at and includes the first occupied square, so captures are included.
+///
+/// ```
+/// use sachy::bitboard::{self, Bitboard};
+/// use sachy::square::Square;
+///
+/// let attacks = bitboard::rook_attacks(Square::from_string("d4").unwrap(), Bitboard::EMPTY);
+/// assert_eq!(attacks.count(), 14);
+/// ```
+pub fn rook_attacks(sq: Square, occupied: Bitboard) -> Bitboard {
+    blocked_ray(sq, Direction::North, occupied)
+        | blocked_ray(sq, Direction::East, occupied)
+        | blocked_ray(sq, Direction::South, occupied)
+        | blocked_ray(sq, Direction::West, occupied)
+}
+
+/// Returns the squares attacked by a bishop on `sq`, given the `occupied` squares of the board.
+///
+/// Each ray stops at and includes the first occupied square, so captures are included.
+///
+/// ```
+/// use sachy::bitboard::{self, Bitboard};
+/// use sachy::square::Square;
+///
+/// let attacks = bitboard::bishop_attacks(Square::from_string("a1").unwrap(), Bitboard::EMPTY);
+/// assert_eq!(attacks.count(), 7);
+/// ```
+pub fn bishop_attacks(sq: Square, occupied: Bitboard) -> Bitboard {
+    blocked_ray(sq, Direction::NorthEast, occupied)
+        | blocked_ray(sq, Direction::SouthEast, occupied)
+        | blocked_ray(sq, Direction::SouthWest, occupied)
+        | blocked_ray(sq, Direction::NorthWest, occupied)
+}
+
+/// Returns the squares attacked by a queen on `sq`, given the `occupied` squares of the board.
+///
+/// This is the union of [`rook_attacks`] and [`bishop_attacks`].
+pub fn queen_attacks(sq: Square, occupied: Bitboard) -> Bitboard {
+    rook_attacks(sq, occupied) | bishop_attacks(sq, occupied)
+}
+
+/// Returns the squares strictly between `a` and `b` if they share a rank, file, or diagonal, and
+/// an empty bitboard otherwise.
+///
+/// ```
+/// use sachy::bitboard;
+/// use sachy::square::Square;
+///
+/// let e1 = Square::from_string("e1").unwrap();
+/// let e4 = Square::from_string("e4").unwrap();
+/// assert_eq!(bitboard::between(e1, e4).count(), 2);
+/// ```
+pub fn between(a: Square, b: Square) -> Bitboard {
+    Direction::ALL
+        .into_iter()
+        .find(|&dir| ray(a, dir).get(b))
+        .map_or(Bitboard::EMPTY, |dir| ray(a, dir) & ray(b, dir.opposite()))
+}
@@ -0,0 +1,386 @@
+//! Positions of pieces on the board.
+
+use crate::bitboard::{self, Bitboard, Direction};
+use crate::color::Color;
+use crate::fen::{self, FenError};
+use crate::piece::{Piece, PieceType};
+use crate::square::Square;
+
+/// The placement of pieces on the board, stored as one bitboard per piece type and one per color.
+///
+/// # Examples
+///
+/// ```
+/// use sachy::board::Board;
+/// use sachy::square::Square;
+/// use sachy::{Color, Piece, PieceType};
+///
+/// let e1 = Square::from_string("e1").unwrap();
+/// let king = Piece::new(Color::White, PieceType::King);
+///
+/// let mut board = Board::empty();
+/// board.set_piece(e1, king);
+/// assert_eq!(board.piece_at(e1), Some(king));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Board {
+    pieces: [Bitboard; 6],
+    colors: [Bitboard; 2],
+}
+
+impl Board {
+    /// Creates a new board with no pieces on it.
+    pub fn empty() -> Board {
+        Board {
+            pieces: [Bitboard::EMPTY; 6],
+            colors: [Bitboard::EMPTY; 2],
+        }
+    }
+
+    /// Creates a board from a FEN string.
+    ///
+    /// Only the piece placement field is parsed, any remaining fields are currently ignored.
+    ///
+    /// ```
+    /// use sachy::board::Board;
+    /// use sachy::square::Square;
+    /// use sachy::{Color, Piece, PieceType};
+    ///
+    /// let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    /// let board = Board::from_fen(fen).unwrap();
+    /// let e1 = Square::from_string("e1").unwrap();
+    /// assert_eq!(board.piece_at(e1), Some(Piece::new(Color::White, PieceType::King)));
+    /// ```
+    pub fn from_fen(fen: &str) -> Result<Board, FenError> {
+        let placement = fen.split_whitespace().next().unwrap_or_default();
+        let ranks: Vec<&str> = placement.split('/').collect();
+
+        if ranks.len() != 8 {
+            return Err(FenError::WrongRankCount);
+        }
+
+        let mut board = Board::empty();
+
+        for (rank, field) in (0..8).rev().zip(ranks) {
+            fen::parse_rank(field, |file, c| {
+                let piece = Piece::from_fen_char(c).ok_or(FenError::InvalidPiece)?;
+                let sq = Square::new(file, rank).map_err(|_| FenError::WrongSquareCount)?;
+                board.set_piece(sq, piece);
+                Ok(())
+            })?;
+        }
+
+        Ok(board)
+    }
+
+    /// Returns the squares occupied by pieces of the given type, of either color.
+    pub fn pieces(&self, kind: PieceType) -> Bitboard { self.pieces[kind.index()] }
+
+    /// Returns the squares occupied by pieces of the given color.
+    pub fn color(&self, color: Color) -> Bitboard { self.colors[color.index()] }
+
+    /// Returns the squares occupied by the given piece.
+    pub fn piece(&self, piece: Piece) -> Bitboard {
+        self.pieces(piece.kind) & self.color(piece.color)
+    }
+
+    /// Returns the squares occupied by any piece.
+    pub fn occupied(&self) -> Bitboard { self.colors[0] | self.colors[1] }
+
+    /// Returns the piece on `sq`, or `None` if the square is empty.
+    pub fn piece_at(&self, sq: Square) -> Option<Piece> {
+        let color = if self.colors[Color::White.index()].get(sq) {
+            Color::White
+        } else if self.colors[Color::Black.index()].get(sq) {
+            Color::Black
+        } else {
+            return None;
+        };
+
+        PieceType::ALL
+            .into_iter()
+            .find(|kind| self.pieces[kind.index()].get(sq))
+            .map(|kind| Piece::new(color, kind))
+    }
+
+    /// Places `piece` on `sq`, replacing any piece already there.
+    pub fn set_piece(&mut self, sq: Square, piece: Piece) {
+        self.remove_piece(sq);
+        self.pieces[piece.kind.index()].set(sq);
+        self.colors[piece.color.index()].set(sq);
+    }
+
+    /// Removes the piece on `sq` and returns it, or `None` if the square was empty.
+    pub fn remove_piece(&mut self, sq: Square) -> Option<Piece> {
+        let piece = self.piece_at(sq)?;
+        self.pieces[piece.kind.index()].clear(sq);
+        self.colors[piece.color.index()].clear(sq);
+        Some(piece)
+    }
+
+    /// Returns whether `color` has bishops on both light and dark squares.
+    pub fn bishop_pair(&self, color: Color) -> bool {
+        let bishops = self.piece(Piece::new(color, PieceType::Bishop));
+        (bishops & Bitboard::LIGHT_SQUARES).any() && (bishops & Bitboard::DARK_SQUARES).any()
+    }
+
+    /// Returns how many squares of `bb` are light squares if `light` is set, or dark squares
+    /// otherwise.
+    ///
+    /// ```
+    /// use sachy::board::Board;
+    /// use sachy::{Color, Piece, PieceType};
+    ///
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/PPPPPPPP/4K3").unwrap();
+    /// let pawns = board.piece(Piece::new(Color::White, PieceType::Pawn));
+    /// assert_eq!(board.count_on_color(pawns, true), 4);
+    /// ```
+    pub fn count_on_color(&self, bb: Bitboard, light: bool) -> u32 {
+        let mask = if light {
+            Bitboard::LIGHT_SQUARES
+        } else {
+            Bitboard::DARK_SQUARES
+        };
+        (bb & mask).count()
+    }
+
+    /// Returns the total [`value`](PieceType::value) of the knights, bishops, rooks and queens of
+    /// `color`.
+    ///
+    /// ```
+    /// use sachy::board::Board;
+    /// use sachy::Color;
+    ///
+    /// let board = Board::from_fen("4k3/pppp4/8/8/8/8/8/R3K1N1").unwrap();
+    /// assert_eq!(board.non_pawn_material(Color::White), 820);
+    /// assert_eq!(board.non_pawn_material(Color::Black), 0);
+    /// ```
+    pub fn non_pawn_material(&self, color: Color) -> i32 {
+        [
+            PieceType::Knight,
+            PieceType::Bishop,
+            PieceType::Rook,
+            PieceType::Queen,
+        ]
+        .into_iter()
+        .map(|kind| self.piece(Piece::new(color, kind)).count() as i32 * kind.value())
+        .sum()
+    }
+
+    /// Returns whether `color` has any pieces other than its king and pawns.
+    pub fn has_non_pawn_material(&self, color: Color) -> bool {
+        let kings_and_pawns = self.pieces(PieceType::King) | self.pieces(PieceType::Pawn);
+        (self.color(color) & !kings_and_pawns).any()
+    }
+
+    /// Returns whether neither side has enough material left to checkmate.
+    ///
+    /// This is the case with kings alone, a single knight or bishop against a bare king, or one
+    /// bishop on each side with both bishops on squares of the same color.
+    ///
+    /// ```
+    /// use sachy::board::Board;
+    ///
+    /// assert!(Board::from_fen("4k3/8/8/8/8/8/8/3BK3").unwrap().is_insufficient_material());
+    /// assert!(!Board::from_fen("4k3/8/8/8/8/8/8/3RK3").unwrap().is_insufficient_material());
+    /// ```
+    pub fn is_insufficient_material(&self) -> bool {
+        let heavy = self.pieces(PieceType::Pawn)
+            | self.pieces(PieceType::Rook)
+            | self.pieces(PieceType::Queen);
+        if heavy.any() {
+            return false;
+        }
+
+        let knights = self.pieces(PieceType::Knight);
+        let bishops = self.pieces(PieceType::Bishop);
+        if (knights | bishops).count() <= 1 {
+            return true;
+        }
+
+        let one_bishop_each = Color::iter().all(|color| (bishops & self.color(color)).count() == 1);
+        let one_color =
+            (bishops & Bitboard::LIGHT_SQUARES).none() || (bishops & Bitboard::DARK_SQUARES).none();
+        knights.none() && one_bishop_each && one_color
+    }
+
+    /// Returns the pieces of either color attacking `sq`, treating only the squares in `occupied`
+    /// as occupied, so that sliders see through pieces removed from it.
+    ///
+    /// ```
+    /// use sachy::board::Board;
+    /// use sachy::square::Square;
+    ///
+    /// let board = Board::from_fen("4k3/8/4p3/3p4/8/2N5/8/4K3").unwrap();
+    /// let d5 = Square::from_string("d5").unwrap();
+    /// assert_eq!(board.attackers_to(d5, board.occupied()).count(), 2);
+    /// ```
+    pub fn attackers_to(&self, sq: Square, occupied: Bitboard) -> Bitboard {
+        let pawns = Color::iter().fold(Bitboard::EMPTY, |pawns, color| {
+            let pawn = Piece::new(color, PieceType::Pawn);
+            pawns | (bitboard::pawn_attacks(sq, color.opposite()) & self.piece(pawn))
+        });
+        let diagonal = self.pieces(PieceType::Bishop) | self.pieces(PieceType::Queen);
+        let straight = self.pieces(PieceType::Rook) | self.pieces(PieceType::Queen);
+
+        let attackers = pawns
+            | (bitboard::knight_attacks(sq) & self.pieces(PieceType::Knight))
+            | (bitboard::king_attacks(sq) & self.pieces(PieceType::King))
+            | (bitboard::bishop_attacks(sq, occupied) & diagonal)
+            | (bitboard::rook_attacks(sq, occupied) & straight);
+        attackers & occupied
+    }
+
+    /// Returns the captures of the exchange on `sq`, in order, as the square each capturing piece
+    /// comes from and its type.
+    ///
+    /// The piece on `sq` is captured by the other color, after which both sides recapture with
+    /// their least valuable attacker for as long as doing so does not lose material. The list is
+    /// empty if `sq` is empty or its piece cannot be captured.
+    ///
+    /// ```
+    /// use sachy::board::Board;
+    /// use sachy::square::Square;
+    /// use sachy::PieceType;
+    ///
+    /// let board = Board::from_fen("4k3/8/4p3/3p4/8/2N5/8/4K3").unwrap();
+    /// let sq = |name| Square::from_string(name).unwrap();
+    /// assert_eq!(
+    ///     board.exchange_sequence(sq("d5")),
+    ///     [(sq("c3"), PieceType::Knight), (sq("e6"), PieceType::Pawn)]
+    /// );
+    /// ```
+    pub fn exchange_sequence(&self, sq: Square) -> Vec<(Square, PieceType)> {
+        let Some(target) = self.piece_at(sq) else {
+            return Vec::new();
+        };
+
+        let mut occupied = self.occupied();
+        let mut side = target.color.opposite();
+        let mut captured = target.kind;
+        let mut captures = Vec::new();
+        let mut values = Vec::new();
+
+        loop {
+            let attackers = self.attackers_to(sq, occupied) & self.color(side);
+            let Some((from, kind)) = PieceType::iter().find_map(|kind| {
+                (attackers & self.pieces(kind))
+                    .lsb()
+                    .map(|from| (from, kind))
+            }) else {
+                break;
+            };
+
+            occupied.clear(from);
+            if kind == PieceType::King
+                && (self.attackers_to(sq, occupied) & self.color(side.opposite())).any()
+            {
+                break;
+            }
+
+            captures.push((from, kind));
+            values.push(captured.value());
+            captured = kind;
+            side = side.opposite();
+        }
+
+        // Walk the swap list backwards: a capture is worth the captured piece, less whatever the
+        // reply wins if the other side chooses to make it.
+        let mut gains = vec![0; values.len()];
+        let mut reply = 0;
+        for (gain, value) in gains.iter_mut().zip(&values).rev() {
+            *gain = value - reply.max(0);
+            reply = *gain;
+        }
+
+        let end = gains
+            .iter()
+            .skip(1)
+            .position(|&gain| gain < 0)
+            .map_or(captures.len(), |stop| stop + 1);
+        captures.truncate(end);
+        captures
+    }
+
+    /// Returns how many attacks `attacker` makes into the ring of squares around the other
+    /// color's king, counting each square once per piece attacking it. Returns 0 if the other
+    /// color has no king.
+    ///
+    /// ```
+    /// use sachy::board::Board;
+    /// use sachy::Color;
+    ///
+    /// let board = Board::from_fen("6k1/R7/8/8/8/8/8/4K3").unwrap();
+    /// assert_eq!(board.king_zone_attacks(Color::White), 3);
+    /// ```
+    pub fn king_zone_attacks(&self, attacker: Color) -> u32 {
+        let king = Piece::new(attacker.opposite(), PieceType::King);
+        let Some(king_sq) = self.piece(king).lsb() else {
+            return 0;
+        };
+
+        let ring = bitboard::king_attacks(king_sq);
+        let occupied = self.occupied();
+        PieceType::iter()
+            .map(|kind| Piece::new(attacker, kind))
+            .flat_map(|piece| self.piece(piece).into_iter().map(move |sq| (piece, sq)))
+            .map(|(piece, sq)| (attacks(piece, sq, occupied) & ring).count())
+            .sum()
+    }
+
+    /// Returns the line through the king that the piece on `sq` is pinned to, running from edge to
+    /// edge of the board, or `None` if the square is empty or its piece is not pinned.
+    ///
+    /// A piece is pinned when it is the only piece between its own king and an enemy rook, bishop
+    /// or queen which attacks along that line.
+    ///
+    /// ```
+    /// use sachy::board::Board;
+    /// use sachy::square::Square;
+    /// use sachy::Bitboard;
+    ///
+    /// let board = Board::from_fen("4r1k1/8/8/8/8/8/4R3/4K3").unwrap();
+    /// let e2 = Square::from_string("e2").unwrap();
+    /// assert_eq!(board.pin_ray(e2), Some(Bitboard::file(4)));
+    /// ```
+    pub fn pin_ray(&self, sq: Square) -> Option<Bitboard> {
+        let piece = self.piece_at(sq)?;
+        let king = self.piece(Piece::new(piece.color, PieceType::King)).lsb()?;
+        let dir = Direction::ALL
+            .into_iter()
+            .find(|&dir| bitboard::ray(king, dir).get(sq))?;
+
+        let diagonal = self.pieces(PieceType::Bishop) | self.pieces(PieceType::Queen);
+        let straight = self.pieces(PieceType::Rook) | self.pieces(PieceType::Queen);
+        let snipers = ((bitboard::bishop_attacks(king, Bitboard::EMPTY) & diagonal)
+            | (bitboard::rook_attacks(king, Bitboard::EMPTY) & straight))
+            & self.color(piece.color.opposite());
+
+        let occupied = self.occupied();
+        snipers
+            .into_iter()
+            .any(|sniper| bitboard::between(king, sniper) & occupied == Bitboard::from(sq))
+            .then(|| {
+                bitboard::ray(king, dir)
+                    | bitboard::ray(king, dir.opposite())
+                    | Bitboard::from(king)
+            })
+    }
+}
+
+impl Default for Board {
+    fn default() -> Board { Board::empty() }
+}
+
+/// Returns the squares attacked by `piece` standing on `sq`, with sliders stopping at the squares in
+/// `occupied`.
+fn attacks(piece: Piece, sq: Square, occupied: Bitboard) -> Bitboard {
+    match piece.kind {
+        | PieceType::Pawn => bitboard::pawn_attacks(sq, piece.color),
+        | PieceType::Knight => bitboard::knight_attacks(sq),
+        | PieceType::Bishop => bitboard::bishop_attacks(sq, occupied),
+        | PieceType::Rook => bitboard::rook_attacks(sq, occupied),
+        | PieceType::Queen => bitboard::queen_attacks(sq, occupied),
+        | PieceType::King => bitboard::king_attacks(sq),
+    }
+}
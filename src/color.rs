@@ -0,0 +1,56 @@
+//! The two sides of a game of chess.
+
+use std::fmt;
+
+/// One of the two sides, white or black.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// The side which moves first, starting on ranks 1 and 2.
+    White,
+    /// The side which moves second, starting on ranks 7 and 8.
+    Black,
+}
+
+impl Color {
+    /// Both colors, white first.
+    pub const ALL: [Color; 2] = [Color::White, Color::Black];
+
+    /// Returns an iterator over both colors, white first.
+    ///
+    /// ```
+    /// use sachy::Color;
+    ///
+    /// let colors: Vec<Color> = Color::iter().collect();
+    /// assert_eq!(colors, [Color::White, Color::Black]);
+    /// ```
+    pub fn iter() -> impl Iterator<Item = Color> { Color::ALL.into_iter() }
+
+    /// Returns the color with the given FEN character, `'w'` for white and `'b'` for black.
+    pub fn from_char(c: char) -> Option<Color> {
+        match c {
+            | 'w' => Some(Color::White),
+            | 'b' => Some(Color::Black),
+            | _ => None,
+        }
+    }
+
+    /// Returns the other color.
+    pub fn opposite(self) -> Color {
+        match self {
+            | Color::White => Color::Black,
+            | Color::Black => Color::White,
+        }
+    }
+
+    /// Returns the index of the color for indexing arrays, 0 for white and 1 for black.
+    pub fn index(self) -> usize { self as usize }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            | Color::White => write!(f, "white"),
+            | Color::Black => write!(f, "black"),
+        }
+    }
+}
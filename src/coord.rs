@@ -0,0 +1,122 @@
+//! Files and ranks of the chess board.
+//!
+//! [`File`] and [`Rank`] name the columns and rows of the board, which makes call sites clearer
+//! than the raw `x` and `y` coordinates of a [`Square`](crate::square::Square).
+
+use std::fmt;
+
+/// A file (column) of the board, from the a-file to the h-file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum File {
+    /// The a-file.
+    FileA,
+    /// The b-file.
+    FileB,
+    /// The c-file.
+    FileC,
+    /// The d-file.
+    FileD,
+    /// The e-file.
+    FileE,
+    /// The f-file.
+    FileF,
+    /// The g-file.
+    FileG,
+    /// The h-file.
+    FileH,
+}
+
+impl File {
+    /// Every file, from the a-file to the h-file.
+    pub const ALL: [File; 8] = [
+        File::FileA,
+        File::FileB,
+        File::FileC,
+        File::FileD,
+        File::FileE,
+        File::FileF,
+        File::FileG,
+        File::FileH,
+    ];
+
+    /// Returns the file with the given index, where 0 is the a-file, or `None` if the index is not
+    /// in the range `0..8`.
+    pub fn from_index(index: u8) -> Option<File> { File::ALL.get(index as usize).copied() }
+
+    /// Returns the file with the given letter, from `'a'` to `'h'`.
+    pub fn from_char(c: char) -> Option<File> {
+        if !('a'..='h').contains(&c) {
+            return None;
+        }
+
+        File::from_index(c as u8 - b'a')
+    }
+
+    /// Returns the index of the file, where 0 is the a-file.
+    pub fn index(self) -> u8 { self as u8 }
+
+    /// Returns the letter of the file, from `'a'` to `'h'`.
+    pub fn to_char(self) -> char { (b'a' + self.index()) as char }
+}
+
+impl fmt::Display for File {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", self.to_char()) }
+}
+
+/// A rank (row) of the board, from the first rank to the eighth rank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rank {
+    /// The first rank.
+    Rank1,
+    /// The second rank.
+    Rank2,
+    /// The third rank.
+    Rank3,
+    /// The fourth rank.
+    Rank4,
+    /// The fifth rank.
+    Rank5,
+    /// The sixth rank.
+    Rank6,
+    /// The seventh rank.
+    Rank7,
+    /// The eighth rank.
+    Rank8,
+}
+
+impl Rank {
+    /// Every rank, from the first rank to the eighth rank.
+    pub const ALL: [Rank; 8] = [
+        Rank::Rank1,
+        Rank::Rank2,
+        Rank::Rank3,
+        Rank::Rank4,
+        Rank::Rank5,
+        Rank::Rank6,
+        Rank::Rank7,
+        Rank::Rank8,
+    ];
+
+    /// Returns the rank with the given index, where 0 is the first rank, or `None` if the index is
+    /// not in the range `0..8`.
+    pub fn from_index(index: u8) -> Option<Rank> { Rank::ALL.get(index as usize).copied() }
+
+    /// Returns the rank with the given digit, from `'1'` to `'8'`.
+    pub fn from_char(c: char) -> Option<Rank> {
+        if !('1'..='8').contains(&c) {
+            return None;
+        }
+
+        Rank::from_index(c as u8 - b'1')
+    }
+
+    /// Returns the index of the rank, where 0 is the first rank.
+    pub fn index(self) -> u8 { self as u8 }
+
+    /// Returns the digit of the rank, from `'1'` to `'8'`.
+    pub fn to_char(self) -> char { (b'1' + self.index()) as char }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "{}", self.to_char()) }
+}
@@ -0,0 +1,62 @@
+//! Parsing of FEN (Forsyth-Edwards Notation) strings.
+
+use std::error::Error;
+use std::fmt;
+
+/// An error which can be returned when parsing a FEN string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FenError {
+    /// The piece placement field does not have exactly eight ranks.
+    WrongRankCount,
+    /// A rank of the piece placement field does not describe exactly eight squares.
+    WrongSquareCount,
+    /// The piece placement field contains a character which is neither a piece nor a digit from 1
+    /// to 8.
+    InvalidPiece,
+    /// The rank to place a field on lies outside of the board.
+    RankOutOfBounds,
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            | FenError::WrongRankCount => write!(f, "piece placement does not have eight ranks"),
+            | FenError::WrongSquareCount => write!(f, "rank does not describe eight squares"),
+            | FenError::InvalidPiece => write!(f, "invalid piece character"),
+            | FenError::RankOutOfBounds => write!(f, "rank is out of bounds"),
+        }
+    }
+}
+
+impl Error for FenError {}
+
+/// Walks a single rank field of a FEN piece placement, such as `"rnbqkbnr"` or `"4P3"`, calling
+/// `occupied` with the file and character of every square which is not skipped by a digit.
+pub(crate) fn parse_rank<F>(field: &str, mut occupied: F) -> Result<(), FenError>
+where
+    F: FnMut(u8, char) -> Result<(), FenError>,
+{
+    let mut file = 0;
+
+    for c in field.chars() {
+        match c.to_digit(10) {
+            | Some(empty @ 1..=8) => file += empty as u8,
+            | Some(_) => return Err(FenError::InvalidPiece),
+            | None if file < 8 => {
+                occupied(file, c)?;
+                file += 1;
+            }
+            | None => return Err(FenError::WrongSquareCount),
+        }
+
+        if file > 8 {
+            return Err(FenError::WrongSquareCount);
+        }
+    }
+
+    if file != 8 {
+        return Err(FenError::WrongSquareCount);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,15 @@
+//! A simple game of chess, written in Rust.
+
+pub mod bitboard;
+pub mod board;
+pub mod color;
+pub mod coord;
+pub mod fen;
+pub mod piece;
+pub mod square;
+
+pub use bitboard::Bitboard;
+pub use board::Board;
+pub use color::Color;
+pub use piece::{Piece, PieceType};
+pub use square::Square;
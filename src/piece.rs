@@ -0,0 +1,123 @@
+//! Chess pieces and their types.
+
+use crate::color::Color;
+
+/// The type of a piece, regardless of its color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PieceType {
+    /// A pawn.
+    Pawn,
+    /// A knight.
+    Knight,
+    /// A bishop.
+    Bishop,
+    /// A rook.
+    Rook,
+    /// A queen.
+    Queen,
+    /// A king.
+    King,
+}
+
+impl PieceType {
+    /// Every piece type, from pawn to king.
+    pub const ALL: [PieceType; 6] = [
+        PieceType::Pawn,
+        PieceType::Knight,
+        PieceType::Bishop,
+        PieceType::Rook,
+        PieceType::Queen,
+        PieceType::King,
+    ];
+
+    /// Returns an iterator over every piece type, from pawn to king.
+    pub fn iter() -> impl Iterator<Item = PieceType> { PieceType::ALL.into_iter() }
+
+    /// Returns the piece type with the given letter in either case, such as `'n'` or `'N'` for a
+    /// knight.
+    pub fn from_char(c: char) -> Option<PieceType> {
+        match c.to_ascii_lowercase() {
+            | 'p' => Some(PieceType::Pawn),
+            | 'n' => Some(PieceType::Knight),
+            | 'b' => Some(PieceType::Bishop),
+            | 'r' => Some(PieceType::Rook),
+            | 'q' => Some(PieceType::Queen),
+            | 'k' => Some(PieceType::King),
+            | _ => None,
+        }
+    }
+
+    /// Returns the lowercase letter of the piece type, such as `'n'` for a knight.
+    pub fn to_char(self) -> char {
+        match self {
+            | PieceType::Pawn => 'p',
+            | PieceType::Knight => 'n',
+            | PieceType::Bishop => 'b',
+            | PieceType::Rook => 'r',
+            | PieceType::Queen => 'q',
+            | PieceType::King => 'k',
+        }
+    }
+
+    /// Returns the index of the piece type for indexing arrays, from 0 for a pawn to 5 for a king.
+    pub fn index(self) -> usize { self as usize }
+
+    /// Returns the material value of the piece type in centipawns, with the king counted as 0.
+    ///
+    /// ```
+    /// use sachy::PieceType;
+    ///
+    /// assert_eq!(PieceType::Pawn.value(), 100);
+    /// assert!(PieceType::Queen.value() > PieceType::Rook.value());
+    /// ```
+    pub fn value(self) -> i32 {
+        match self {
+            | PieceType::Pawn => 100,
+            | PieceType::Knight => 320,
+            | PieceType::Bishop => 330,
+            | PieceType::Rook => 500,
+            | PieceType::Queen => 900,
+            | PieceType::King => 0,
+        }
+    }
+}
+
+/// A piece of a given color and type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Piece {
+    /// The side the piece belongs to.
+    pub color: Color,
+    /// The type of the piece.
+    pub kind: PieceType,
+}
+
+impl Piece {
+    /// Creates a new piece of the given color and type.
+    pub fn new(color: Color, kind: PieceType) -> Piece { Piece { color, kind } }
+
+    /// Returns the piece with the given FEN character, uppercase for white and lowercase for black.
+    ///
+    /// ```
+    /// use sachy::piece::{Piece, PieceType};
+    /// use sachy::Color;
+    ///
+    /// assert_eq!(Piece::from_fen_char('N'), Some(Piece::new(Color::White, PieceType::Knight)));
+    /// assert_eq!(Piece::from_fen_char('q'), Some(Piece::new(Color::Black, PieceType::Queen)));
+    /// ```
+    pub fn from_fen_char(c: char) -> Option<Piece> {
+        let color = if c.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        };
+        PieceType::from_char(c).map(|kind| Piece::new(color, kind))
+    }
+
+    /// Returns the FEN character of the piece, uppercase for white and lowercase for black.
+    pub fn to_fen_char(self) -> char {
+        match self.color {
+            | Color::White => self.kind.to_char().to_ascii_uppercase(),
+            | Color::Black => self.kind.to_char(),
+        }
+    }
+}
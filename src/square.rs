@@ -0,0 +1,464 @@
+//! Squares of the chess board.
+//!
+//! A [`Square`] is addressed by its file `x` (0 for the a-file through 7 for the h-file) and its
+//! rank `y` (0 for the first rank through 7 for the eighth rank). Squares are numbered from 0 (a1)
+//! to 63 (h8), running along each rank before moving up to the next one.
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::bitboard::Direction;
+use crate::color::Color;
+use crate::coord::{File, Rank};
+
+/// A single square of the chess board.
+///
+/// The file is packed into the high nibble and the rank into the low nibble of the inner value.
+///
+/// # Examples
+///
+/// ```
+/// use sachy::square::Square;
+///
+/// let e4 = Square::from_string("e4").unwrap();
+/// assert_eq!(e4.x(), 4);
+/// assert_eq!(e4.y(), 3);
+/// assert_eq!(e4.index(), 28);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Square {
+    val: u8,
+}
+
+/// A signed displacement between two squares, in files (`dx`) and ranks (`dy`).
+///
+/// Adding an offset to a [`Square`] yields `None` if the result is off the board.
+///
+/// ```
+/// use sachy::square::{Offset, Square};
+///
+/// let g1 = Square::from_string("g1").unwrap();
+/// assert_eq!(g1 + Offset { dx: -1, dy: 2 }, Square::from_string("f3").ok());
+/// assert_eq!(g1 + Offset { dx: 2, dy: 1 }, None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Offset {
+    /// The number of files towards the h-file.
+    pub dx: i8,
+    /// The number of ranks towards rank 8.
+    pub dy: i8,
+}
+
+/// The color of a square of the board, with a1 being dark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SquareColor {
+    /// A light square, such as h1.
+    Light,
+    /// A dark square, such as a1.
+    Dark,
+}
+
+/// An error which can be returned when constructing a [`Square`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SquareError {
+    /// The coordinates or index lie outside of the board.
+    OutOfBounds,
+    /// The string is not a valid square in algebraic notation.
+    InvalidString,
+}
+
+impl fmt::Display for SquareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            | SquareError::OutOfBounds => write!(f, "square is out of bounds"),
+            | SquareError::InvalidString => write!(f, "invalid square string"),
+        }
+    }
+}
+
+impl Error for SquareError {}
+
+impl Square {
+    /// Every square of the board, in index order from a1 to h8.
+    pub const ALL: [Square; 64] = {
+        let mut squares = [Square { val: 0 }; 64];
+        let mut index = 0;
+        while index < 64 {
+            squares[index] = Square {
+                val: (((index % 8) << 4) | (index / 8)) as u8,
+            };
+            index += 1;
+        }
+        squares
+    };
+
+    /// Creates a new square from its file `x` and rank `y`, both in the range `0..8`.
+    pub fn new(x: u8, y: u8) -> Result<Square, SquareError> {
+        if x > 7 || y > 7 {
+            return Err(SquareError::OutOfBounds);
+        }
+
+        Ok(Square { val: (x << 4) | y })
+    }
+
+    /// Creates a new square from its file and rank.
+    pub fn from_file_rank(file: File, rank: Rank) -> Square {
+        Square {
+            val: (file.index() << 4) | rank.index(),
+        }
+    }
+
+    /// Creates a new square from its index in the range `0..64`, where 0 is a1 and 63 is h8.
+    pub fn from_index(index: u8) -> Result<Square, SquareError> {
+        if index > 63 {
+            return Err(SquareError::OutOfBounds);
+        }
+
+        Square::new(index % 8, index / 8)
+    }
+
+    /// Returns an iterator over every square of the board, in index order from a1 to h8.
+    pub fn all() -> impl Iterator<Item = Square> { Square::ALL.into_iter() }
+
+    /// Creates a new square from its name in algebraic notation, such as `"e4"`.
+    pub fn from_string(s: &str) -> Result<Square, SquareError> {
+        let mut chars = s.chars();
+        let (Some(file), Some(rank), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(SquareError::InvalidString);
+        };
+
+        match (File::from_char(file), Rank::from_char(rank)) {
+            | (Some(file), Some(rank)) => Ok(Square::from_file_rank(file, rank)),
+            | _ => Err(SquareError::InvalidString),
+        }
+    }
+
+    /// Parses a pair of squares written back to back in algebraic notation, such as `"e2e4"`.
+    ///
+    /// ```
+    /// use sachy::square::Square;
+    ///
+    /// let (from, to) = Square::parse_pair("g1f3").unwrap();
+    /// assert_eq!(from, Square::from_string("g1").unwrap());
+    /// assert_eq!(to, Square::from_string("f3").unwrap());
+    /// ```
+    pub fn parse_pair(s: &str) -> Result<(Square, Square), SquareError> {
+        let (Some(from), Some(to)) = (s.get(..2), s.get(2..)) else {
+            return Err(SquareError::InvalidString);
+        };
+
+        Ok((Square::from_string(from)?, Square::from_string(to)?))
+    }
+
+    /// Returns the file of the square, where 0 is the a-file.
+    pub fn x(&self) -> u8 { self.val >> 4 }
+
+    /// Returns the rank of the square, where 0 is the first rank.
+    pub fn y(&self) -> u8 { self.val & 0x0f }
+
+    /// Returns the file of the square.
+    pub fn file(&self) -> File { File::ALL[self.x() as usize] }
+
+    /// Returns the rank of the square.
+    pub fn rank(&self) -> Rank { Rank::ALL[self.y() as usize] }
+
+    /// Returns the index of the diagonal (running from a1 towards h8) the square lies on, in the
+    /// range `0..15`, where 0 is the diagonal of a8 alone, 7 is the long a1-h8 diagonal and 14
+    /// is the diagonal of h1 alone.
+    pub fn diagonal(&self) -> u8 { 7 + self.x() - self.y() }
+
+    /// Returns the index of the anti-diagonal (running from h1 towards a8) the square lies on, in
+    /// the range `0..15`, where 0 is the anti-diagonal of a1 alone and 7 is the long h1-a8
+    /// anti-diagonal.
+    pub fn anti_diagonal(&self) -> u8 { self.x() + self.y() }
+
+    /// Returns whether the square shares a rank with `other`.
+    pub fn same_rank(&self, other: Square) -> bool { self.y() == other.y() }
+
+    /// Returns whether the square shares a file with `other`.
+    pub fn same_file(&self, other: Square) -> bool { self.x() == other.x() }
+
+    /// Returns whether the square shares a diagonal with `other`.
+    pub fn same_diagonal(&self, other: Square) -> bool { self.diagonal() == other.diagonal() }
+
+    /// Returns whether the square shares an anti-diagonal with `other`.
+    pub fn same_anti_diagonal(&self, other: Square) -> bool {
+        self.anti_diagonal() == other.anti_diagonal()
+    }
+
+    /// Returns whether the square shares a rank, file, diagonal, or anti-diagonal with `other`.
+    pub fn aligned(&self, other: Square) -> bool {
+        self.same_rank(other)
+            || self.same_file(other)
+            || self.same_diagonal(other)
+            || self.same_anti_diagonal(other)
+    }
+
+    /// Returns the index of the square in the range `0..64`.
+    pub fn index(&self) -> u8 { self.y() * 8 + self.x() }
+
+    /// Returns the square flipped vertically, so that a1 becomes a8.
+    pub fn flip_vertical(&self) -> Square {
+        Square {
+            val: self.val ^ 0x07,
+        }
+    }
+
+    /// Returns the square mirrored horizontally, so that a1 becomes h1.
+    pub fn mirror_horizontal(&self) -> Square {
+        Square {
+            val: self.val ^ 0x70,
+        }
+    }
+
+    /// Returns the square rotated by 180 degrees, so that a1 becomes h8.
+    pub fn rotate_180(&self) -> Square {
+        Square {
+            val: self.val ^ 0x77,
+        }
+    }
+
+    /// Returns the square as seen from the perspective of `color`, unchanged for white and
+    /// flipped vertically for black.
+    pub fn relative(&self, color: Color) -> Square {
+        match color {
+            | Color::White => *self,
+            | Color::Black => self.flip_vertical(),
+        }
+    }
+
+    /// Returns whether the square is on the rank where pawns of `color` promote, rank 8 for white
+    /// and rank 1 for black.
+    pub fn is_promotion_rank(self, color: Color) -> bool { self.relative(color).y() == 7 }
+
+    /// Returns whether the square is on the rank where pawns of `color` start, rank 2 for white
+    /// and rank 7 for black.
+    pub fn is_starting_pawn_rank(self, color: Color) -> bool { self.relative(color).y() == 1 }
+
+    /// Returns the square `dx` files and `dy` ranks away, or `None` if it is off the board.
+    fn shifted(&self, dx: i8, dy: i8) -> Option<Square> {
+        let x = u8::try_from((self.x() as i8).checked_add(dx)?).ok()?;
+        let y = u8::try_from((self.y() as i8).checked_add(dy)?).ok()?;
+        Square::new(x, y).ok()
+    }
+
+    /// Returns the square one rank north, or `None` on rank 8.
+    pub fn north(&self) -> Option<Square> { self.shifted(0, 1) }
+
+    /// Returns the square one rank south, or `None` on rank 1.
+    pub fn south(&self) -> Option<Square> { self.shifted(0, -1) }
+
+    /// Returns the square one file east, or `None` on the h-file.
+    pub fn east(&self) -> Option<Square> { self.shifted(1, 0) }
+
+    /// Returns the square one file west, or `None` on the a-file.
+    pub fn west(&self) -> Option<Square> { self.shifted(-1, 0) }
+
+    /// Returns the square one step north-east, or `None` on rank 8 or the h-file.
+    pub fn north_east(&self) -> Option<Square> { self.shifted(1, 1) }
+
+    /// Returns the square one step north-west, or `None` on rank 8 or the a-file.
+    pub fn north_west(&self) -> Option<Square> { self.shifted(-1, 1) }
+
+    /// Returns the square one step south-east, or `None` on rank 1 or the h-file.
+    pub fn south_east(&self) -> Option<Square> { self.shifted(1, -1) }
+
+    /// Returns the square one step south-west, or `None` on rank 1 or the a-file.
+    pub fn south_west(&self) -> Option<Square> { self.shifted(-1, -1) }
+
+    /// Returns the adjacent square in the direction `dir`, or `None` if it is off the board.
+    ///
+    /// ```
+    /// use sachy::bitboard::Direction;
+    /// use sachy::square::Square;
+    ///
+    /// let e4 = Square::from_string("e4").unwrap();
+    /// assert_eq!(e4.step(Direction::NorthEast), Square::from_string("f5").ok());
+    /// ```
+    pub fn step(self, dir: Direction) -> Option<Square> {
+        match dir {
+            | Direction::North => self.north(),
+            | Direction::NorthEast => self.north_east(),
+            | Direction::East => self.east(),
+            | Direction::SouthEast => self.south_east(),
+            | Direction::South => self.south(),
+            | Direction::SouthWest => self.south_west(),
+            | Direction::West => self.west(),
+            | Direction::NorthWest => self.north_west(),
+        }
+    }
+
+    /// Returns the number of files separating the square from `other`.
+    pub fn file_distance(&self, other: Square) -> u8 { self.x().abs_diff(other.x()) }
+
+    /// Returns the number of ranks separating the square from `other`.
+    pub fn rank_distance(&self, other: Square) -> u8 { self.y().abs_diff(other.y()) }
+
+    /// Returns the Chebyshev distance to `other`, the number of king moves between the two squares
+    /// on an empty board.
+    pub fn distance(&self, other: Square) -> u8 {
+        Square::distance_table()[self.index() as usize][other.index() as usize]
+    }
+
+    /// Returns the table of Chebyshev distances between every pair of squares, indexed by square
+    /// index.
+    ///
+    /// ```
+    /// use sachy::square::Square;
+    ///
+    /// let (a1, h8) = (Square::from_string("a1").unwrap(), Square::from_string("h8").unwrap());
+    /// assert_eq!(Square::distance_table()[a1.index() as usize][h8.index() as usize], 7);
+    /// ```
+    pub fn distance_table() -> &'static [[u8; 64]; 64] {
+        static TABLE: OnceLock<[[u8; 64]; 64]> = OnceLock::new();
+
+        TABLE.get_or_init(|| {
+            let mut table = [[0; 64]; 64];
+
+            for a in Square::ALL {
+                for b in Square::ALL {
+                    table[a.index() as usize][b.index() as usize] =
+                        a.file_distance(b).max(a.rank_distance(b));
+                }
+            }
+
+            table
+        })
+    }
+
+    /// Returns the Manhattan distance to `other`, the sum of the file and rank separations.
+    pub fn manhattan_distance(&self, other: Square) -> u8 {
+        self.file_distance(other) + self.rank_distance(other)
+    }
+
+    /// Returns whether the square is light or dark.
+    pub fn color(&self) -> SquareColor {
+        if (self.x() + self.y()).is_multiple_of(2) {
+            SquareColor::Dark
+        } else {
+            SquareColor::Light
+        }
+    }
+
+    /// Returns the pixel coordinates `(x, y)` of the top-left corner of the square when the board
+    /// is drawn with square cells of `cell` pixels. The board is drawn from white's side, with a1
+    /// in the bottom-left corner, unless `flipped` is set, in which case a1 is in the top-right.
+    ///
+    /// ```
+    /// use sachy::square::Square;
+    ///
+    /// let a1 = Square::from_string("a1").unwrap();
+    /// assert_eq!(a1.to_screen(false, 50), (0, 350));
+    /// assert_eq!(a1.to_screen(true, 50), (350, 0));
+    /// ```
+    pub fn to_screen(self, flipped: bool, cell: u32) -> (u32, u32) {
+        let (col, row) = if flipped {
+            (7 - self.x(), self.y())
+        } else {
+            (self.x(), 7 - self.y())
+        };
+        (col as u32 * cell, row as u32 * cell)
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.file(), self.rank())
+    }
+}
+
+impl fmt::Debug for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Square at ({}, {})", self.x(), self.y())
+    }
+}
+
+impl Add<Offset> for Square {
+    type Output = Option<Square>;
+
+    fn add(self, rhs: Offset) -> Option<Square> { self.shifted(rhs.dx, rhs.dy) }
+}
+
+impl Sub<Offset> for Square {
+    type Output = Option<Square>;
+
+    fn sub(self, rhs: Offset) -> Option<Square> {
+        self.shifted(rhs.dx.checked_neg()?, rhs.dy.checked_neg()?)
+    }
+}
+
+/// Squares are ordered by index, so a1 < b1 < ... < h1 < a2 < ... < h8.
+impl Ord for Square {
+    fn cmp(&self, other: &Square) -> Ordering { self.index().cmp(&other.index()) }
+}
+
+impl PartialOrd for Square {
+    fn partial_cmp(&self, other: &Square) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl FromStr for Square {
+    type Err = SquareError;
+
+    fn from_str(s: &str) -> Result<Square, SquareError> { Square::from_string(s) }
+}
+
+impl TryFrom<u8> for Square {
+    type Error = SquareError;
+
+    fn try_from(index: u8) -> Result<Square, SquareError> { Square::from_index(index) }
+}
+
+impl TryFrom<(u8, u8)> for Square {
+    type Error = SquareError;
+
+    fn try_from((x, y): (u8, u8)) -> Result<Square, SquareError> { Square::new(x, y) }
+}
+
+/// Squares serialize as their name in algebraic notation for human-readable formats, and as their
+/// index otherwise.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Square {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u8(self.index())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Square {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Square, D::Error> {
+        struct SquareVisitor;
+
+        impl serde::de::Visitor<'_> for SquareVisitor {
+            type Value = Square;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a square in algebraic notation or a square index")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Square, E> {
+                Square::from_string(v).map_err(E::custom)
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Square, E> {
+                u8::try_from(v)
+                    .map_err(|_| SquareError::OutOfBounds)
+                    .and_then(Square::from_index)
+                    .map_err(E::custom)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(SquareVisitor)
+        } else {
+            deserializer.deserialize_u8(SquareVisitor)
+        }
+    }
+}
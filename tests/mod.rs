@@ -1 +1,2026 @@
-// This module contains all of the unit and integration tests for the `sachy.rs` library.
\ No newline at end of file
+// This module contains all of the unit and integration tests for the `sachy.rs` library.
+
+mod piece {
+    use sachy::{Color, Piece, PieceType};
+
+    #[test]
+    fn fen_char_round_trip() {
+        let chars = "PNBRQKpnbrqk";
+        let mut pieces = Vec::new();
+        for color in Color::iter() {
+            for kind in PieceType::ALL {
+                pieces.push(Piece::new(color, kind));
+            }
+        }
+
+        for (piece, c) in pieces.into_iter().zip(chars.chars()) {
+            assert_eq!(piece.to_fen_char(), c);
+            assert_eq!(Piece::from_fen_char(c), Some(piece));
+        }
+    }
+
+    #[test]
+    fn from_fen_char_invalid() {
+        for c in ['x', 'X', '1', ' ', '/'] {
+            assert_eq!(Piece::from_fen_char(c), None);
+        }
+    }
+
+    #[test]
+    fn piece_type_from_char() {
+        assert_eq!(PieceType::from_char('n'), Some(PieceType::Knight));
+        assert_eq!(PieceType::from_char('N'), Some(PieceType::Knight));
+        assert_eq!(PieceType::from_char('x'), None);
+
+        for kind in PieceType::ALL {
+            assert_eq!(PieceType::from_char(kind.to_char()), Some(kind));
+        }
+    }
+
+    #[test]
+    fn piece_type_index() {
+        for (i, kind) in PieceType::ALL.into_iter().enumerate() {
+            assert_eq!(kind.index(), i);
+        }
+    }
+
+    #[test]
+    fn piece_type_iter() {
+        let kinds: Vec<PieceType> = PieceType::iter().collect();
+        assert_eq!(
+            kinds,
+            [
+                PieceType::Pawn,
+                PieceType::Knight,
+                PieceType::Bishop,
+                PieceType::Rook,
+                PieceType::Queen,
+                PieceType::King,
+            ]
+        );
+    }
+
+    #[test]
+    fn piece_type_value() {
+        assert_eq!(PieceType::Pawn.value(), 100);
+        assert!(PieceType::Knight.value() > PieceType::Pawn.value());
+        assert!(PieceType::Bishop.value() >= PieceType::Knight.value());
+        assert!(PieceType::Rook.value() > PieceType::Bishop.value());
+        assert!(PieceType::Queen.value() > PieceType::Rook.value());
+        assert_eq!(PieceType::King.value(), 0);
+    }
+}
+
+mod square {
+    use std::collections::{BTreeSet, HashSet};
+
+    use sachy::bitboard::Direction;
+    use sachy::square::{Offset, Square, SquareColor, SquareError};
+    use sachy::{Bitboard, Color};
+
+    fn sq(name: &str) -> Square { Square::from_string(name).unwrap() }
+
+    #[test]
+    fn new_in_bounds() {
+        let sq = Square::new(4, 3).unwrap();
+        assert_eq!(sq.x(), 4);
+        assert_eq!(sq.y(), 3);
+        assert_eq!(sq.index(), 28);
+    }
+
+    #[test]
+    fn new_out_of_bounds() {
+        assert_eq!(Square::new(8, 0), Err(SquareError::OutOfBounds));
+        assert_eq!(Square::new(0, 8), Err(SquareError::OutOfBounds));
+    }
+
+    #[test]
+    fn from_index_round_trip() {
+        for i in 0..64 {
+            assert_eq!(Square::from_index(i).unwrap().index(), i);
+        }
+
+        assert_eq!(Square::from_index(64), Err(SquareError::OutOfBounds));
+    }
+
+    #[test]
+    fn from_string() {
+        assert_eq!(
+            Square::from_string("a1").unwrap(),
+            Square::new(0, 0).unwrap()
+        );
+        assert_eq!(
+            Square::from_string("h8").unwrap(),
+            Square::new(7, 7).unwrap()
+        );
+        assert_eq!(Square::from_string("i1"), Err(SquareError::InvalidString));
+        assert_eq!(Square::from_string("a9"), Err(SquareError::InvalidString));
+        assert_eq!(Square::from_string("e44"), Err(SquareError::InvalidString));
+        assert_eq!(Square::from_string(""), Err(SquareError::InvalidString));
+    }
+
+    #[test]
+    fn display_and_debug() {
+        let sq = Square::from_string("e4").unwrap();
+        assert_eq!(sq.to_string(), "e4");
+        assert_eq!(format!("{:?}", sq), "Square at (4, 3)");
+    }
+
+    #[test]
+    fn all_in_index_order() {
+        assert_eq!(Square::ALL.len(), 64);
+
+        for (i, sq) in Square::ALL.iter().enumerate() {
+            assert_eq!(sq.index() as usize, i);
+            assert_eq!(*sq, Square::from_index(i as u8).unwrap());
+        }
+    }
+
+    #[test]
+    fn all_iterator() {
+        let squares: Vec<Square> = Square::all().collect();
+        let distinct: HashSet<Square> = squares.iter().copied().collect();
+
+        assert_eq!(squares.len(), 64);
+        assert_eq!(distinct.len(), 64);
+        assert_eq!(squares.first(), Square::from_string("a1").ok().as_ref());
+        assert_eq!(squares.last(), Square::from_string("h8").ok().as_ref());
+    }
+
+    #[test]
+    fn color_corners() {
+        let color = |name| Square::from_string(name).unwrap().color();
+        assert_eq!(color("a1"), SquareColor::Dark);
+        assert_eq!(color("h1"), SquareColor::Light);
+        assert_eq!(color("a8"), SquareColor::Light);
+        assert_eq!(color("h8"), SquareColor::Dark);
+    }
+
+    #[test]
+    fn color_center() {
+        let color = |name| Square::from_string(name).unwrap().color();
+        assert_eq!(color("d4"), SquareColor::Dark);
+        assert_eq!(color("e4"), SquareColor::Light);
+        assert_eq!(color("d5"), SquareColor::Light);
+        assert_eq!(color("e5"), SquareColor::Dark);
+    }
+
+    #[test]
+    fn color_balanced() {
+        let dark = Square::all()
+            .filter(|sq| sq.color() == SquareColor::Dark)
+            .count();
+        assert_eq!(dark, 32);
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(sq("e4").distance(sq("e5")), 1);
+        assert_eq!(sq("e4").distance(sq("f5")), 1);
+        assert_eq!(sq("a1").distance(sq("h8")), 7);
+        assert_eq!(sq("b1").distance(sq("c7")), 6);
+        assert_eq!(sq("d4").distance(sq("d4")), 0);
+    }
+
+    #[test]
+    fn distance_symmetric() {
+        for a in Square::all() {
+            for b in Square::all() {
+                assert_eq!(a.distance(b), b.distance(a));
+            }
+        }
+    }
+
+    #[test]
+    fn manhattan_distance() {
+        assert_eq!(sq("a1").manhattan_distance(sq("h8")), 14);
+        assert_eq!(sq("e4").manhattan_distance(sq("f5")), 2);
+        assert_eq!(sq("h1").manhattan_distance(sq("a2")), 8);
+    }
+
+    #[test]
+    fn manhattan_distance_same_square() {
+        for sq in Square::all() {
+            assert_eq!(sq.manhattan_distance(sq), 0);
+        }
+    }
+
+    #[test]
+    fn manhattan_distance_symmetric() {
+        for a in Square::all() {
+            for b in Square::all() {
+                assert_eq!(a.manhattan_distance(b), b.manhattan_distance(a));
+            }
+        }
+    }
+
+    #[test]
+    fn file_and_rank_distance() {
+        assert_eq!(sq("a1").file_distance(sq("h8")), 7);
+        assert_eq!(sq("a1").rank_distance(sq("h8")), 7);
+        assert_eq!(sq("c2").file_distance(sq("f7")), 3);
+        assert_eq!(sq("c2").rank_distance(sq("f7")), 5);
+        assert_eq!(sq("f7").file_distance(sq("c2")), 3);
+        assert_eq!(sq("f7").rank_distance(sq("c2")), 5);
+        assert_eq!(sq("e4").file_distance(sq("e8")), 0);
+        assert_eq!(sq("b4").rank_distance(sq("g4")), 0);
+    }
+
+    #[test]
+    fn neighbors_center() {
+        let d4 = sq("d4");
+        assert_eq!(d4.north(), Some(sq("d5")));
+        assert_eq!(d4.south(), Some(sq("d3")));
+        assert_eq!(d4.east(), Some(sq("e4")));
+        assert_eq!(d4.west(), Some(sq("c4")));
+        assert_eq!(d4.north_east(), Some(sq("e5")));
+        assert_eq!(d4.north_west(), Some(sq("c5")));
+        assert_eq!(d4.south_east(), Some(sq("e3")));
+        assert_eq!(d4.south_west(), Some(sq("c3")));
+    }
+
+    #[test]
+    fn neighbors_edges() {
+        let h8 = Square::new(7, 7).unwrap();
+        assert_eq!(h8.north(), None);
+        assert_eq!(h8.east(), None);
+        assert_eq!(h8.north_east(), None);
+        assert_eq!(h8.north_west(), None);
+        assert_eq!(h8.south_east(), None);
+        assert_eq!(h8.south(), Square::new(7, 6).ok());
+
+        let a1 = Square::new(0, 0).unwrap();
+        assert_eq!(a1.south(), None);
+        assert_eq!(a1.west(), None);
+        assert_eq!(a1.south_west(), None);
+        assert_eq!(a1.south_east(), None);
+        assert_eq!(a1.north_west(), None);
+        assert_eq!(a1.north_east(), Square::new(1, 1).ok());
+    }
+
+    #[test]
+    fn reflections() {
+        assert_eq!(sq("a1").flip_vertical(), sq("a8"));
+        assert_eq!(sq("a1").mirror_horizontal(), sq("h1"));
+        assert_eq!(sq("a1").rotate_180(), sq("h8"));
+        assert_eq!(sq("c2").flip_vertical(), sq("c7"));
+        assert_eq!(sq("c2").mirror_horizontal(), sq("f2"));
+        assert_eq!(sq("c2").rotate_180(), sq("f7"));
+    }
+
+    #[test]
+    fn reflections_are_involutions() {
+        for sq in Square::all() {
+            assert_eq!(sq.flip_vertical().flip_vertical(), sq);
+            assert_eq!(sq.mirror_horizontal().mirror_horizontal(), sq);
+            assert_eq!(sq.rotate_180().rotate_180(), sq);
+            assert_eq!(sq.flip_vertical().mirror_horizontal(), sq.rotate_180());
+        }
+    }
+
+    #[test]
+    fn reflections_match_bitboard() {
+        for sq in Square::all() {
+            let bb = Bitboard::from(sq);
+            assert_eq!(bb.flip_vertical(), Bitboard::from(sq.flip_vertical()));
+            assert_eq!(
+                bb.mirror_horizontal(),
+                Bitboard::from(sq.mirror_horizontal())
+            );
+        }
+    }
+
+    #[test]
+    fn relative() {
+        assert_eq!(sq("e2").relative(Color::White), sq("e2"));
+        assert_eq!(sq("e2").relative(Color::Black), sq("e7"));
+        assert_eq!(sq("a8").relative(Color::Black), sq("a1"));
+
+        for sq in Square::all() {
+            assert_eq!(sq.relative(Color::Black).relative(Color::Black), sq);
+        }
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("e4".parse::<Square>().unwrap(), Square::new(4, 3).unwrap());
+        assert_eq!("z9".parse::<Square>(), Err(SquareError::InvalidString));
+    }
+
+    #[test]
+    fn try_from_index() {
+        assert_eq!(Square::try_from(28u8), Square::from_string("e4"));
+        assert_eq!(Square::try_from(64u8), Err(SquareError::OutOfBounds));
+
+        let squares: Result<Vec<Square>, _> =
+            [0u8, 9, 63].into_iter().map(Square::try_from).collect();
+        assert_eq!(squares.unwrap().len(), 3);
+
+        let squares: Result<Vec<Square>, _> = [0u8, 99].into_iter().map(Square::try_from).collect();
+        assert_eq!(squares, Err(SquareError::OutOfBounds));
+    }
+
+    #[test]
+    fn try_from_coordinates() {
+        assert_eq!(Square::try_from((4, 3)), Square::from_string("e4"));
+        assert_eq!(Square::try_from((8, 3)), Err(SquareError::OutOfBounds));
+        assert_eq!(Square::try_from((3, 8)), Err(SquareError::OutOfBounds));
+    }
+
+    #[test]
+    fn ordering_by_index() {
+        assert!(sq("a1") < sq("b1"));
+        assert!(sq("h1") < sq("a2"));
+        assert!(sq("g8") < sq("h8"));
+
+        let mut squares: Vec<Square> = Square::all().map(|sq| sq.rotate_180()).collect();
+        squares.swap(3, 40);
+        squares.swap(17, 62);
+        squares.sort();
+        assert_eq!(squares, Square::ALL);
+    }
+
+    #[test]
+    fn btree_set() {
+        let set: BTreeSet<Square> = ["h8", "a2", "h1", "a1"]
+            .into_iter()
+            .map(|name| Square::from_string(name).unwrap())
+            .collect();
+        let names: Vec<String> = set.iter().map(Square::to_string).collect();
+        assert_eq!(names, ["a1", "h1", "a2", "h8"]);
+    }
+
+    #[test]
+    fn add_offset() {
+        assert_eq!(sq("e2") + Offset { dx: 0, dy: 2 }, Some(sq("e4")));
+        assert_eq!(sq("b1") + Offset { dx: 1, dy: 2 }, Some(sq("c3")));
+        assert_eq!(sq("e4") + Offset { dx: 0, dy: 0 }, Some(sq("e4")));
+    }
+
+    #[test]
+    fn add_offset_off_board() {
+        assert_eq!(sq("h4") + Offset { dx: 1, dy: 0 }, None);
+        assert_eq!(sq("a4") + Offset { dx: -1, dy: 0 }, None);
+        assert_eq!(sq("d7") + Offset { dx: 0, dy: 2 }, None);
+        assert_eq!(sq("d1") + Offset { dx: 1, dy: -1 }, None);
+        assert_eq!(
+            sq("d4")
+                + Offset {
+                    dx: i8::MAX,
+                    dy: i8::MIN
+                },
+            None
+        );
+    }
+
+    #[test]
+    fn sub_offset() {
+        assert_eq!(sq("e4") - Offset { dx: 0, dy: 2 }, Some(sq("e2")));
+        assert_eq!(sq("c3") - Offset { dx: 1, dy: 2 }, Some(sq("b1")));
+        assert_eq!(sq("a1") - Offset { dx: 1, dy: 0 }, None);
+        assert_eq!(sq("h8") - Offset { dx: 0, dy: -1 }, None);
+        assert_eq!(sq("d4") - Offset { dx: i8::MIN, dy: 0 }, None);
+    }
+
+    #[test]
+    fn diagonals() {
+        assert_eq!(sq("a1").diagonal(), sq("h8").diagonal());
+        assert_eq!(sq("a1").diagonal(), 7);
+        assert_eq!(sq("h1").diagonal(), 14);
+        assert_eq!(sq("a8").diagonal(), 0);
+        assert_eq!(sq("b1").diagonal(), sq("g6").diagonal());
+        assert_ne!(sq("a1").diagonal(), sq("b1").diagonal());
+    }
+
+    #[test]
+    fn anti_diagonals() {
+        assert_eq!(sq("a8").anti_diagonal(), sq("h1").anti_diagonal());
+        assert_eq!(sq("a8").anti_diagonal(), 7);
+        assert_eq!(sq("a1").anti_diagonal(), 0);
+        assert_eq!(sq("h8").anti_diagonal(), 14);
+        assert_eq!(sq("c1").anti_diagonal(), sq("a3").anti_diagonal());
+        assert_ne!(sq("a8").anti_diagonal(), sq("b8").anti_diagonal());
+    }
+
+    #[test]
+    fn diagonal_ranges() {
+        for sq in Square::all() {
+            assert!(sq.diagonal() < 15);
+            assert!(sq.anti_diagonal() < 15);
+        }
+    }
+
+    #[test]
+    fn same_line_predicates() {
+        assert!(sq("a4").same_rank(sq("h4")));
+        assert!(!sq("a4").same_rank(sq("a5")));
+        assert!(sq("e1").same_file(sq("e8")));
+        assert!(!sq("e1").same_file(sq("f1")));
+        assert!(sq("c1").same_diagonal(sq("h6")));
+        assert!(!sq("c1").same_diagonal(sq("a3")));
+        assert!(sq("c1").same_anti_diagonal(sq("a3")));
+        assert!(!sq("c1").same_anti_diagonal(sq("h6")));
+    }
+
+    #[test]
+    fn aligned() {
+        assert!(sq("d4").aligned(sq("d8")));
+        assert!(sq("d4").aligned(sq("a4")));
+        assert!(sq("d4").aligned(sq("g7")));
+        assert!(sq("d4").aligned(sq("a7")));
+        assert!(!sq("d4").aligned(sq("e6")));
+        assert!(!sq("a1").aligned(sq("b3")));
+    }
+
+    #[test]
+    fn aligned_matches_rays() {
+        for a in Square::all() {
+            let lines = sachy::bitboard::queen_attacks(a, Bitboard::EMPTY);
+            for b in Square::all().filter(|&b| b != a) {
+                assert_eq!(a.aligned(b), lines.get(b));
+            }
+        }
+    }
+
+    #[test]
+    fn to_screen() {
+        assert_eq!(sq("a1").to_screen(false, 60), (0, 420));
+        assert_eq!(sq("h8").to_screen(false, 60), (420, 0));
+        assert_eq!(sq("e4").to_screen(false, 60), (240, 240));
+        assert_eq!(sq("a1").to_screen(true, 60), (420, 0));
+        assert_eq!(sq("h8").to_screen(true, 60), (0, 420));
+        assert_eq!(sq("e4").to_screen(true, 60), (180, 180));
+    }
+
+    #[test]
+    fn step_center() {
+        let d4 = sq("d4");
+        assert_eq!(d4.step(Direction::North), Some(sq("d5")));
+        assert_eq!(d4.step(Direction::NorthEast), Some(sq("e5")));
+        assert_eq!(d4.step(Direction::East), Some(sq("e4")));
+        assert_eq!(d4.step(Direction::SouthEast), Some(sq("e3")));
+        assert_eq!(d4.step(Direction::South), Some(sq("d3")));
+        assert_eq!(d4.step(Direction::SouthWest), Some(sq("c3")));
+        assert_eq!(d4.step(Direction::West), Some(sq("c4")));
+        assert_eq!(d4.step(Direction::NorthWest), Some(sq("c5")));
+    }
+
+    #[test]
+    fn step_edges() {
+        for dir in [Direction::North, Direction::NorthEast, Direction::East] {
+            assert_eq!(sq("h8").step(dir), None);
+        }
+        for dir in [Direction::South, Direction::SouthWest, Direction::West] {
+            assert_eq!(sq("a1").step(dir), None);
+        }
+        assert_eq!(sq("a8").step(Direction::NorthWest), None);
+        assert_eq!(sq("h1").step(Direction::SouthEast), None);
+        assert_eq!(sq("h8").step(Direction::SouthWest), Some(sq("g7")));
+    }
+
+    #[test]
+    fn step_matches_shift() {
+        for sq in Square::all() {
+            for dir in Direction::ALL {
+                let step = sq.step(dir).map(Bitboard::from).unwrap_or_default();
+                assert_eq!(step, dir.shift(Bitboard::from(sq)));
+            }
+        }
+    }
+
+    #[test]
+    fn distance_table() {
+        let table = Square::distance_table();
+        for a in Square::all() {
+            for b in Square::all() {
+                let expected = a.file_distance(b).max(a.rank_distance(b));
+                assert_eq!(table[a.index() as usize][b.index() as usize], expected);
+                assert_eq!(a.distance(b), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn parse_pair() {
+        assert_eq!(Square::parse_pair("e2e4"), Ok((sq("e2"), sq("e4"))));
+        assert_eq!(Square::parse_pair("h8a1"), Ok((sq("h8"), sq("a1"))));
+    }
+
+    #[test]
+    fn parse_pair_invalid() {
+        for s in ["", "e2", "e2e", "e2e4q", "e2i4", "z2e4", "é2e4", "e2e4 "] {
+            assert_eq!(
+                Square::parse_pair(s),
+                Err(SquareError::InvalidString),
+                "{s:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn promotion_rank() {
+        assert!(sq("e8").is_promotion_rank(Color::White));
+        assert!(sq("a1").is_promotion_rank(Color::Black));
+        assert!(!sq("e8").is_promotion_rank(Color::Black));
+        assert!(!sq("a1").is_promotion_rank(Color::White));
+        assert!(!sq("e7").is_promotion_rank(Color::White));
+
+        for color in Color::iter() {
+            let count = Square::all()
+                .filter(|sq| sq.is_promotion_rank(color))
+                .count();
+            assert_eq!(count, 8);
+        }
+    }
+
+    #[test]
+    fn starting_pawn_rank() {
+        assert!(sq("d2").is_starting_pawn_rank(Color::White));
+        assert!(sq("d7").is_starting_pawn_rank(Color::Black));
+        assert!(!sq("d2").is_starting_pawn_rank(Color::Black));
+        assert!(!sq("d7").is_starting_pawn_rank(Color::White));
+        assert!(!sq("d3").is_starting_pawn_rank(Color::White));
+
+        for color in Color::iter() {
+            let count = Square::all()
+                .filter(|sq| sq.is_starting_pawn_rank(color))
+                .count();
+            assert_eq!(count, 8);
+        }
+    }
+}
+
+mod board {
+    use sachy::fen::FenError;
+    use sachy::square::Square;
+    use sachy::{Bitboard, Board, Color, Piece, PieceType};
+
+    fn sq(name: &str) -> Square { Square::from_string(name).unwrap() }
+
+    #[test]
+    fn empty() {
+        let board = Board::empty();
+        assert!(board.occupied().none());
+
+        for sq in Square::all() {
+            assert_eq!(board.piece_at(sq), None);
+        }
+    }
+
+    #[test]
+    fn set_and_query() {
+        let white_king = Piece::new(Color::White, PieceType::King);
+        let black_knight = Piece::new(Color::Black, PieceType::Knight);
+
+        let mut board = Board::empty();
+        board.set_piece(sq("e1"), white_king);
+        board.set_piece(sq("g8"), black_knight);
+
+        assert_eq!(board.piece_at(sq("e1")), Some(white_king));
+        assert_eq!(board.piece_at(sq("g8")), Some(black_knight));
+        assert_eq!(board.piece_at(sq("e2")), None);
+
+        assert_eq!(board.occupied().count(), 2);
+        assert_eq!(board.color(Color::White), Bitboard::from(sq("e1")));
+        assert_eq!(board.color(Color::Black), Bitboard::from(sq("g8")));
+        assert_eq!(board.pieces(PieceType::Knight), Bitboard::from(sq("g8")));
+        assert_eq!(board.piece(white_king), Bitboard::from(sq("e1")));
+        assert!(board
+            .piece(Piece::new(Color::Black, PieceType::King))
+            .none());
+    }
+
+    #[test]
+    fn set_replaces() {
+        let white_pawn = Piece::new(Color::White, PieceType::Pawn);
+        let black_queen = Piece::new(Color::Black, PieceType::Queen);
+
+        let mut board = Board::empty();
+        board.set_piece(sq("d5"), white_pawn);
+        board.set_piece(sq("d5"), black_queen);
+
+        assert_eq!(board.piece_at(sq("d5")), Some(black_queen));
+        assert!(board.pieces(PieceType::Pawn).none());
+        assert!(board.color(Color::White).none());
+    }
+
+    #[test]
+    fn remove() {
+        let white_rook = Piece::new(Color::White, PieceType::Rook);
+
+        let mut board = Board::empty();
+        board.set_piece(sq("a1"), white_rook);
+
+        assert_eq!(board.remove_piece(sq("a1")), Some(white_rook));
+        assert_eq!(board.remove_piece(sq("a1")), None);
+        assert_eq!(board, Board::empty());
+    }
+
+    const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    #[test]
+    fn from_fen_start_position() {
+        let board = Board::from_fen(START_FEN).unwrap();
+
+        assert_eq!(
+            board.piece_at(sq("e1")),
+            Some(Piece::new(Color::White, PieceType::King))
+        );
+        assert_eq!(
+            board.piece_at(sq("d8")),
+            Some(Piece::new(Color::Black, PieceType::Queen))
+        );
+        assert_eq!(
+            board.piece_at(sq("b1")),
+            Some(Piece::new(Color::White, PieceType::Knight))
+        );
+        assert_eq!(
+            board.piece_at(sq("h8")),
+            Some(Piece::new(Color::Black, PieceType::Rook))
+        );
+        assert_eq!(board.piece_at(sq("e4")), None);
+
+        assert_eq!(board.occupied().count(), 32);
+        assert_eq!(
+            board.color(Color::White),
+            Bitboard::rank(0) | Bitboard::rank(1)
+        );
+        assert_eq!(
+            board.color(Color::Black),
+            Bitboard::rank(6) | Bitboard::rank(7)
+        );
+        assert_eq!(
+            board.pieces(PieceType::Pawn),
+            Bitboard::rank(1) | Bitboard::rank(6)
+        );
+    }
+
+    #[test]
+    fn from_fen_placement_only() {
+        let board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K2R").unwrap();
+
+        assert_eq!(board.occupied().count(), 5);
+        assert_eq!(
+            board.piece_at(sq("e8")),
+            Some(Piece::new(Color::Black, PieceType::King))
+        );
+        assert_eq!(
+            board.piece_at(sq("d5")),
+            Some(Piece::new(Color::Black, PieceType::Pawn))
+        );
+        assert_eq!(
+            board.piece_at(sq("e5")),
+            Some(Piece::new(Color::White, PieceType::Pawn))
+        );
+        assert_eq!(
+            board.piece_at(sq("h1")),
+            Some(Piece::new(Color::White, PieceType::Rook))
+        );
+    }
+
+    #[test]
+    fn from_fen_wrong_rank_count() {
+        assert_eq!(Board::from_fen(""), Err(FenError::WrongRankCount));
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8 w - - 0 1"),
+            Err(FenError::WrongRankCount)
+        );
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8/8/8 w - - 0 1"),
+            Err(FenError::WrongRankCount)
+        );
+    }
+
+    #[test]
+    fn from_fen_wrong_square_count() {
+        let short = "rnbqkbn/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(Board::from_fen(short), Err(FenError::WrongSquareCount));
+
+        let long = "rnbqkbnr/ppppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(Board::from_fen(long), Err(FenError::WrongSquareCount));
+
+        let overflow = "rnbqkbnr/pppppppp/44p/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(Board::from_fen(overflow), Err(FenError::WrongSquareCount));
+
+        let empty_rank = "rnbqkbnr//8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(Board::from_fen(empty_rank), Err(FenError::WrongSquareCount));
+    }
+
+    #[test]
+    fn from_fen_invalid_piece() {
+        let fen = "rnbqkbnr/pppppppp/8/8/3X4/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(Board::from_fen(fen), Err(FenError::InvalidPiece));
+
+        let zero = "rnbqkbnr/pppppppp/08/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(Board::from_fen(zero), Err(FenError::InvalidPiece));
+
+        let nine = "rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(Board::from_fen(nine), Err(FenError::InvalidPiece));
+    }
+
+    #[test]
+    fn bishop_pair() {
+        let board = Board::from_fen(START_FEN).unwrap();
+        assert!(board.bishop_pair(Color::White));
+        assert!(board.bishop_pair(Color::Black));
+
+        let board = Board::from_fen("rn1qkbnr/pppppppp/8/8/8/4B3/PPPPPPPP/RN1QK1NR").unwrap();
+        assert!(!board.bishop_pair(Color::White));
+        assert!(!board.bishop_pair(Color::Black));
+
+        let board = Board::from_fen("4k3/8/8/8/8/4B3/8/2B1K3").unwrap();
+        assert!(!board.bishop_pair(Color::White));
+    }
+
+    #[test]
+    fn count_on_color() {
+        let board = Board::from_fen("4k3/1p3p2/2p5/8/3P4/8/PP3PPP/4K3").unwrap();
+        let white_pawns = board.piece(Piece::new(Color::White, PieceType::Pawn));
+        assert_eq!(board.count_on_color(white_pawns, true), 2);
+        assert_eq!(board.count_on_color(white_pawns, false), 4);
+
+        let black_pawns = board.piece(Piece::new(Color::Black, PieceType::Pawn));
+        assert_eq!(board.count_on_color(black_pawns, true), 3);
+        assert_eq!(board.count_on_color(black_pawns, false), 0);
+    }
+
+    #[test]
+    fn non_pawn_material() {
+        let board = Board::from_fen("4k3/p4p2/8/8/8/8/2P3P1/4K3").unwrap();
+        for color in Color::iter() {
+            assert!(!board.has_non_pawn_material(color));
+            assert_eq!(board.non_pawn_material(color), 0);
+        }
+
+        let board = Board::from_fen("4k3/p4p2/8/8/8/5N2/2P3P1/4K3").unwrap();
+        assert!(board.has_non_pawn_material(Color::White));
+        assert!(!board.has_non_pawn_material(Color::Black));
+        assert_eq!(
+            board.non_pawn_material(Color::White),
+            PieceType::Knight.value()
+        );
+
+        let board = Board::from_fen(START_FEN).unwrap();
+        assert_eq!(
+            board.non_pawn_material(Color::White),
+            2 * (320 + 330 + 500) + 900
+        );
+        assert_eq!(
+            board.non_pawn_material(Color::Black),
+            board.non_pawn_material(Color::White)
+        );
+    }
+
+    #[test]
+    fn insufficient_material_minor_pieces() {
+        for fen in [
+            "4k3/8/8/8/8/8/8/4K3",
+            "4k3/8/8/8/8/8/8/1N2K3",
+            "4k3/8/8/8/8/8/8/2B1K3",
+        ] {
+            assert!(
+                Board::from_fen(fen).unwrap().is_insufficient_material(),
+                "{fen}"
+            );
+        }
+
+        for fen in [
+            "4k3/8/8/8/8/8/4P3/4K3",
+            "4k3/8/8/8/8/8/8/R3K3",
+            "4k3/8/8/8/8/8/8/3QK3",
+            "4k3/8/8/8/8/8/8/1NN1K3",
+            "4k1n1/8/8/8/8/8/8/2B1K3",
+        ] {
+            assert!(
+                !Board::from_fen(fen).unwrap().is_insufficient_material(),
+                "{fen}"
+            );
+        }
+    }
+
+    #[test]
+    fn insufficient_material_same_color_bishops() {
+        // c1 and f8 are both dark squares.
+        let board = Board::from_fen("4kb2/8/8/8/8/8/8/2B1K3").unwrap();
+        assert!(board.is_insufficient_material());
+    }
+
+    #[test]
+    fn insufficient_material_opposite_color_bishops() {
+        // c1 is a dark square and c8 a light one.
+        let board = Board::from_fen("2b1k3/8/8/8/8/8/8/2B1K3").unwrap();
+        assert!(!board.is_insufficient_material());
+    }
+
+    #[test]
+    fn insufficient_material_two_bishops_against_none() {
+        // c1 and e3 are both dark squares.
+        let board = Board::from_fen("4k3/8/8/8/8/4B3/8/2B1K3").unwrap();
+        assert!(!board.is_insufficient_material());
+    }
+
+    #[test]
+    fn attackers_to() {
+        let board = Board::from_fen("3rk3/8/4p3/3p4/4P3/2N5/3R4/3RK3").unwrap();
+        let occupied = board.occupied();
+        let attackers = board.attackers_to(sq("d5"), occupied);
+        let expected = [sq("d8"), sq("e6"), sq("e4"), sq("c3"), sq("d2")];
+        assert_eq!(attackers, expected.into_iter().collect());
+
+        let mut without_d2 = occupied;
+        without_d2.clear(sq("d2"));
+        assert!(board.attackers_to(sq("d5"), without_d2).get(sq("d1")));
+        assert!(board.attackers_to(sq("a5"), occupied).none());
+    }
+
+    #[test]
+    fn exchange_sequence_pawn_defended_by_pawn() {
+        let board = Board::from_fen("4k3/8/4p3/3p4/8/2N5/8/4K3").unwrap();
+        assert_eq!(
+            board.exchange_sequence(sq("d5")),
+            [(sq("c3"), PieceType::Knight), (sq("e6"), PieceType::Pawn)]
+        );
+    }
+
+    #[test]
+    fn exchange_sequence_x_ray() {
+        let board = Board::from_fen("3rk3/8/8/3p4/8/8/3R4/3RK3").unwrap();
+        assert_eq!(
+            board.exchange_sequence(sq("d5")),
+            [
+                (sq("d2"), PieceType::Rook),
+                (sq("d8"), PieceType::Rook),
+                (sq("d1"), PieceType::Rook),
+            ]
+        );
+    }
+
+    #[test]
+    fn exchange_sequence_stops_before_losing_recapture() {
+        let board = Board::from_fen("3qk3/8/8/3n4/4P3/8/8/3RK3").unwrap();
+        assert_eq!(
+            board.exchange_sequence(sq("d5")),
+            [(sq("e4"), PieceType::Pawn)]
+        );
+    }
+
+    #[test]
+    fn exchange_sequence_king_cannot_capture_defended_piece() {
+        let board = Board::from_fen("4k3/8/4p3/3p4/4K3/8/8/8").unwrap();
+        assert!(board.exchange_sequence(sq("d5")).is_empty());
+        assert!(board.exchange_sequence(sq("a4")).is_empty());
+    }
+
+    #[test]
+    fn king_zone_attacks() {
+        let quiet = Board::from_fen("6k1/5ppp/8/8/8/8/PPP5/QK1R4").unwrap();
+        let aimed = Board::from_fen("6k1/5ppp/8/8/8/8/PPP5/1K4RQ").unwrap();
+        assert_eq!(quiet.king_zone_attacks(Color::White), 0);
+        assert!(aimed.king_zone_attacks(Color::White) > quiet.king_zone_attacks(Color::White));
+        assert_eq!(aimed.king_zone_attacks(Color::Black), 0);
+        assert_eq!(Board::empty().king_zone_attacks(Color::White), 0);
+    }
+
+    #[test]
+    fn pin_ray_bishop_pinned_rook() {
+        let board = Board::from_fen("4k3/8/8/1b6/8/3R4/8/5K2").unwrap();
+        let diagonal: Bitboard = ["f1", "e2", "d3", "c4", "b5", "a6"]
+            .into_iter()
+            .map(sq)
+            .collect();
+        assert_eq!(board.pin_ray(sq("d3")), Some(diagonal));
+    }
+
+    #[test]
+    fn pin_ray_rook_pinned_on_file_and_rank() {
+        let board = Board::from_fen("4r1k1/8/8/8/8/8/4R3/r2NK3").unwrap();
+        assert_eq!(board.pin_ray(sq("e2")), Some(Bitboard::file(4)));
+        assert_eq!(board.pin_ray(sq("d1")), Some(Bitboard::rank(0)));
+    }
+
+    #[test]
+    fn pin_ray_unpinned() {
+        let board = Board::from_fen(START_FEN).unwrap();
+        for sq in board.occupied() {
+            assert_eq!(board.pin_ray(sq), None);
+        }
+
+        // Two pieces stand between the king and the rook on e8, and the rook on a5 cannot pin
+        // the bishop on d2 along a diagonal.
+        let board = Board::from_fen("4r1k1/8/8/r7/8/4N3/3BR3/4K3").unwrap();
+        assert_eq!(board.pin_ray(sq("e2")), None);
+        assert_eq!(board.pin_ray(sq("e3")), None);
+        assert_eq!(board.pin_ray(sq("d2")), None);
+        assert_eq!(board.pin_ray(sq("a4")), None);
+        assert_eq!(board.pin_ray(sq("g8")), None);
+    }
+}
+
+mod color {
+    use sachy::Color;
+
+    #[test]
+    fn opposite() {
+        assert_eq!(Color::White.opposite(), Color::Black);
+        assert_eq!(Color::Black.opposite(), Color::White);
+    }
+
+    #[test]
+    fn index() {
+        assert_eq!(Color::White.index(), 0);
+        assert_eq!(Color::Black.index(), 1);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Color::White.to_string(), "white");
+        assert_eq!(Color::Black.to_string(), "black");
+    }
+
+    #[test]
+    fn from_char() {
+        assert_eq!(Color::from_char('w'), Some(Color::White));
+        assert_eq!(Color::from_char('b'), Some(Color::Black));
+        assert_eq!(Color::from_char('W'), None);
+        assert_eq!(Color::from_char('B'), None);
+        assert_eq!(Color::from_char('x'), None);
+    }
+
+    #[test]
+    fn iter() {
+        let colors: Vec<Color> = Color::iter().collect();
+        assert_eq!(colors, [Color::White, Color::Black]);
+        assert_eq!(Color::ALL, [Color::White, Color::Black]);
+
+        for (index, color) in Color::iter().enumerate() {
+            assert_eq!(color.index(), index);
+        }
+    }
+}
+
+mod coord {
+    use sachy::coord::{File, Rank};
+    use sachy::square::Square;
+
+    #[test]
+    fn file_char_round_trip() {
+        for (file, c) in File::ALL.into_iter().zip('a'..='h') {
+            assert_eq!(file.to_char(), c);
+            assert_eq!(File::from_char(c), Some(file));
+            assert_eq!(file.to_string(), c.to_string());
+        }
+
+        assert_eq!(File::from_char('i'), None);
+        assert_eq!(File::from_char('A'), None);
+    }
+
+    #[test]
+    fn rank_char_round_trip() {
+        for (rank, c) in Rank::ALL.into_iter().zip('1'..='8') {
+            assert_eq!(rank.to_char(), c);
+            assert_eq!(Rank::from_char(c), Some(rank));
+            assert_eq!(rank.to_string(), c.to_string());
+        }
+
+        assert_eq!(Rank::from_char('0'), None);
+        assert_eq!(Rank::from_char('9'), None);
+    }
+
+    #[test]
+    fn index_round_trip() {
+        for i in 0..8 {
+            assert_eq!(File::from_index(i).map(File::index), Some(i));
+            assert_eq!(Rank::from_index(i).map(Rank::index), Some(i));
+        }
+
+        assert_eq!(File::from_index(8), None);
+        assert_eq!(Rank::from_index(8), None);
+    }
+
+    #[test]
+    fn square_file_and_rank() {
+        let e4 = Square::from_string("e4").unwrap();
+        assert_eq!(e4.file(), File::FileE);
+        assert_eq!(e4.rank(), Rank::Rank4);
+        assert_eq!(Square::from_file_rank(File::FileE, Rank::Rank4), e4);
+
+        for sq in Square::all() {
+            assert_eq!(sq.file().index(), sq.x());
+            assert_eq!(sq.rank().index(), sq.y());
+            assert_eq!(Square::from_file_rank(sq.file(), sq.rank()), sq);
+        }
+    }
+}
+
+mod bitboard {
+    use std::collections::{HashMap, HashSet};
+
+    use sachy::bitboard::{self, Direction, GridError};
+    use sachy::fen::FenError;
+    use sachy::square::SquareColor;
+    use sachy::{Bitboard, Board, Color, Square};
+
+    fn squares(names: &[&str]) -> Bitboard {
+        let mut bb = Bitboard::new();
+        for name in names {
+            bb.set(Square::from_string(name).unwrap());
+        }
+        bb
+    }
+
+    #[test]
+    fn set_get_clear() {
+        let e4 = Square::from_string("e4").unwrap();
+        let mut bb = Bitboard::new();
+        assert!(bb.none());
+
+        bb.set(e4);
+        assert!(bb.get(e4));
+        assert!(bb.any());
+        assert_eq!(bb.count(), 1);
+
+        bb.clear(e4);
+        assert!(!bb.get(e4));
+        assert!(bb.none());
+    }
+
+    #[test]
+    fn put() {
+        let a1 = Square::from_string("a1").unwrap();
+        let mut bb = Bitboard::new();
+
+        bb.put(a1, true);
+        assert_eq!(bb.bits, 1);
+
+        bb.put(a1, false);
+        assert_eq!(bb.bits, 0);
+    }
+
+    #[test]
+    fn equality() {
+        let mut a = Bitboard::new();
+        let mut b = Bitboard::new();
+        assert!(a == b);
+
+        for name in ["a1", "e4", "h8"] {
+            a.set(Square::from_string(name).unwrap());
+        }
+        for name in ["h8", "a1", "e4"] {
+            b.set(Square::from_string(name).unwrap());
+        }
+        assert!(a == b);
+
+        b.clear(Square::from_string("e4").unwrap());
+        assert!(a != b);
+    }
+
+    #[test]
+    fn hash_map_key() {
+        let mut a = Bitboard::new();
+        a.set(Square::from_string("d4").unwrap());
+        let b = a;
+
+        let mut map = HashMap::new();
+        map.insert(a, "d4");
+        assert_eq!(map.get(&b), Some(&"d4"));
+    }
+
+    #[test]
+    fn debug_grid() {
+        let mut bb = Bitboard::new();
+        bb.set(Square::from_string("a1").unwrap());
+        bb.set(Square::from_string("h8").unwrap());
+
+        let expected = "Bitboard:\n\
+                        0 0 0 0 0 0 0 1\n\
+                        0 0 0 0 0 0 0 0\n\
+                        0 0 0 0 0 0 0 0\n\
+                        0 0 0 0 0 0 0 0\n\
+                        0 0 0 0 0 0 0 0\n\
+                        0 0 0 0 0 0 0 0\n\
+                        0 0 0 0 0 0 0 0\n\
+                        1 0 0 0 0 0 0 0";
+        assert_eq!(format!("{:?}", bb), expected);
+    }
+
+    #[test]
+    fn bitwise_operators() {
+        let a = squares(&["a1", "b2", "c3"]);
+        let b = squares(&["b2", "c3", "d4"]);
+
+        assert_eq!(a | b, squares(&["a1", "b2", "c3", "d4"]));
+        assert_eq!(a & b, squares(&["b2", "c3"]));
+        assert_eq!(a ^ b, squares(&["a1", "d4"]));
+        assert_eq!((!a).count(), 61);
+        assert!((!a & a).none());
+
+        let (ra, rb) = (&a, &b);
+        assert_eq!(ra | rb, a | b);
+        assert_eq!(ra & rb, a & b);
+        assert_eq!(ra ^ rb, a ^ b);
+        assert_eq!(!ra, !a);
+    }
+
+    #[test]
+    fn bitwise_assign_operators() {
+        let a = squares(&["a1", "b2", "c3"]);
+        let b = squares(&["b2", "c3", "d4"]);
+
+        let mut c = a;
+        c |= b;
+        assert_eq!(c, a | b);
+
+        let mut c = a;
+        c &= &b;
+        assert_eq!(c, a & b);
+
+        let mut c = a;
+        c ^= b;
+        assert_eq!(c, a ^ b);
+    }
+
+    #[test]
+    fn raw_shifts() {
+        let a1 = squares(&["a1"]);
+        assert_eq!(a1 << 9, squares(&["b2"]));
+        assert_eq!(squares(&["b2"]) >> 9, a1);
+        assert!((squares(&["h8"]) << 1).none());
+    }
+
+    #[test]
+    fn raw_shifts_past_the_board() {
+        assert_eq!(Bitboard::FULL << 64, Bitboard::EMPTY);
+        assert_eq!(Bitboard::FULL >> 64, Bitboard::EMPTY);
+        assert_eq!(Bitboard::FULL << 100, Bitboard::EMPTY);
+    }
+
+    #[test]
+    fn directional_shifts() {
+        let d4 = squares(&["d4"]);
+        assert_eq!(d4.north(), squares(&["d5"]));
+        assert_eq!(d4.south(), squares(&["d3"]));
+        assert_eq!(d4.east(), squares(&["e4"]));
+        assert_eq!(d4.west(), squares(&["c4"]));
+        assert_eq!(d4.north_east(), squares(&["e5"]));
+        assert_eq!(d4.north_west(), squares(&["c5"]));
+        assert_eq!(d4.south_east(), squares(&["e3"]));
+        assert_eq!(d4.south_west(), squares(&["c3"]));
+    }
+
+    #[test]
+    fn directional_shifts_drop_off_board() {
+        assert!(squares(&["a8", "h8"]).north().none());
+        assert!(squares(&["a1", "h1"]).south().none());
+        assert!(squares(&["h1", "h8"]).east().none());
+        assert!(squares(&["a1", "a8"]).west().none());
+        assert!(squares(&["h4"]).north_east().none());
+        assert!(squares(&["a4"]).north_west().none());
+        assert!(squares(&["h4"]).south_east().none());
+        assert!(squares(&["a4"]).south_west().none());
+    }
+
+    #[test]
+    fn directional_shifts_chain() {
+        let pawns = Bitboard::rank(1);
+        let empty = !squares(&["c3", "e4"]);
+        let single = pawns.north() & empty;
+        let double = single.north() & empty & Bitboard::rank(3);
+        assert_eq!(single, Bitboard::rank(2) & !squares(&["c3"]));
+        assert_eq!(double, Bitboard::rank(3) & !squares(&["c4", "e4"]));
+        assert_eq!(
+            squares(&["a1"]).north().north().east().east(),
+            squares(&["c3"])
+        );
+    }
+
+    #[test]
+    fn directional_shifts_forward_chain() {
+        let blockers = squares(&["c3", "e4", "d6", "f5"]);
+        let empty = !blockers;
+        let cases = [
+            (Color::White, 1, 3, squares(&["c3", "c4", "e4"])),
+            (Color::Black, 6, 4, squares(&["d6", "d5", "f5"])),
+        ];
+
+        for (color, start, target, blocked) in cases {
+            let pawns = Bitboard::rank(start);
+            let single = pawns.forward(color) & empty;
+            let double = single.forward(color) & empty & Bitboard::rank(target);
+            assert_eq!(double, Bitboard::rank(target) & !blocked);
+        }
+
+        let pawns = squares(&["a2", "h2"]);
+        assert_eq!(pawns.forward(Color::White).east() & empty, squares(&["b3"]));
+        assert_eq!(
+            squares(&["d7"])
+                .forward(Color::Black)
+                .west()
+                .forward(Color::Black),
+            squares(&["c5"])
+        );
+    }
+
+    #[test]
+    fn empty_and_full() {
+        assert!(Bitboard::EMPTY.none());
+        assert_eq!(Bitboard::EMPTY, Bitboard::new());
+        assert!(Bitboard::FULL.any());
+        assert_eq!(Bitboard::FULL.count(), 64);
+        assert_eq!(!Bitboard::FULL, Bitboard::EMPTY);
+    }
+
+    #[test]
+    fn grid_round_trip() {
+        for bits in [
+            0,
+            u64::MAX,
+            1,
+            1 << 63,
+            0x0123_4567_89ab_cdef,
+            0x00ff_0000_0000_ff00,
+        ] {
+            let bb = Bitboard::from(bits);
+            assert_eq!(Bitboard::from_grid(&bb.to_string()), Ok(bb));
+        }
+    }
+
+    #[test]
+    fn grid_literal() {
+        let grid = "0 0 0 0 0 0 0 1
+                    0 0 0 0 0 0 0 0
+                    0 0 0 0 0 0 0 0
+                    0 0 0 0 0 0 0 0
+                    0 0 0 0 0 0 0 0
+                    0 0 0 0 0 0 0 0
+                    0 1 0 0 0 0 0 0
+                    1 0 0 0 0 0 0 0";
+        assert_eq!(Bitboard::from_grid(grid), Ok(squares(&["a1", "b2", "h8"])));
+    }
+
+    #[test]
+    fn grid_malformed() {
+        let rank = "0 0 0 0 0 0 0 0\n";
+
+        assert_eq!(Bitboard::from_grid(""), Err(GridError::WrongRankCount));
+        assert_eq!(
+            Bitboard::from_grid(&rank.repeat(7)),
+            Err(GridError::WrongRankCount)
+        );
+        assert_eq!(
+            Bitboard::from_grid(&rank.repeat(9)),
+            Err(GridError::WrongRankCount)
+        );
+
+        let short = format!("0 0 0 0 0 0 0\n{}", rank.repeat(7));
+        assert_eq!(Bitboard::from_grid(&short), Err(GridError::WrongFileCount));
+
+        let long = format!("0 0 0 0 0 0 0 0 0\n{}", rank.repeat(7));
+        assert_eq!(Bitboard::from_grid(&long), Err(GridError::WrongFileCount));
+
+        let invalid = format!("0 0 0 2 0 0 0 0\n{}", rank.repeat(7));
+        assert_eq!(Bitboard::from_grid(&invalid), Err(GridError::InvalidCell));
+    }
+
+    #[test]
+    fn into_iter_ascending() {
+        let bb = squares(&["h8", "e4", "a1", "b1"]);
+        let names: Vec<String> = bb.into_iter().map(|sq| sq.to_string()).collect();
+        assert_eq!(names, ["a1", "b1", "e4", "h8"]);
+    }
+
+    #[test]
+    fn into_iter_exact_size() {
+        let bb = squares(&["c3", "d4", "e5"]);
+        let mut iter = (&bb).into_iter();
+        assert_eq!(iter.len(), 3);
+        iter.next();
+        assert_eq!(iter.len(), 2);
+
+        assert_eq!(Bitboard::EMPTY.into_iter().next(), None);
+        assert_eq!(Bitboard::FULL.into_iter().len(), 64);
+    }
+
+    #[test]
+    fn for_loop() {
+        let bb = Bitboard::FULL;
+        let mut expected = 0;
+        for sq in &bb {
+            assert_eq!(sq.index(), expected);
+            expected += 1;
+        }
+        assert_eq!(expected, 64);
+    }
+
+    #[test]
+    fn collect_round_trip() {
+        let input: Vec<Square> = ["a1", "d4", "g7", "h8"]
+            .iter()
+            .map(|name| Square::from_string(name).unwrap())
+            .collect();
+
+        let bb: Bitboard = input.iter().copied().collect();
+        assert_eq!(bb.count(), 4);
+        assert_eq!(bb.into_iter().collect::<Vec<_>>(), input);
+    }
+
+    #[test]
+    fn collect_duplicates() {
+        let e4 = Square::from_string("e4").unwrap();
+        let bb: Bitboard = [e4, e4, e4].into_iter().collect();
+        assert_eq!(bb, squares(&["e4"]));
+    }
+
+    #[test]
+    fn extend() {
+        let mut bb = squares(&["a1"]);
+        bb.extend([
+            Square::from_string("a1").unwrap(),
+            Square::from_string("b2").unwrap(),
+        ]);
+        assert_eq!(bb, squares(&["a1", "b2"]));
+    }
+
+    #[test]
+    fn pop_lsb() {
+        let mut bb = squares(&["c3", "a1"]);
+        assert_eq!(bb.pop_lsb(), Square::from_string("a1").ok());
+        assert_eq!(bb, squares(&["c3"]));
+        assert_eq!(bb.pop_lsb(), Square::from_string("c3").ok());
+        assert_eq!(bb.pop_lsb(), None);
+    }
+
+    #[test]
+    fn pop_lsb_full() {
+        let mut bb = Bitboard::FULL;
+        for i in 0..64 {
+            assert_eq!(bb.pop_lsb().map(|sq| sq.index()), Some(i));
+        }
+        assert_eq!(bb.pop_lsb(), None);
+        assert!(bb.none());
+    }
+
+    #[test]
+    fn lsb_msb_single() {
+        let bb = squares(&["e4"]);
+        assert_eq!(bb.lsb(), Square::from_string("e4").ok());
+        assert_eq!(bb.msb(), Square::from_string("e4").ok());
+    }
+
+    #[test]
+    fn lsb_msb_multiple() {
+        let bb = squares(&["b2", "e4", "g7"]);
+        assert_eq!(bb.lsb(), Square::from_string("b2").ok());
+        assert_eq!(bb.msb(), Square::from_string("g7").ok());
+        assert_eq!(bb.count(), 3);
+
+        assert_eq!(Bitboard::FULL.lsb(), Square::from_string("a1").ok());
+        assert_eq!(Bitboard::FULL.msb(), Square::from_string("h8").ok());
+    }
+
+    #[test]
+    fn lsb_msb_empty() {
+        assert_eq!(Bitboard::EMPTY.lsb(), None);
+        assert_eq!(Bitboard::EMPTY.msb(), None);
+    }
+
+    #[test]
+    fn rank_masks() {
+        for r in 0..8 {
+            let rank = Bitboard::rank(r);
+            assert_eq!(rank, Bitboard::RANKS[r as usize]);
+            assert_eq!(rank.count(), 8);
+
+            for sq in rank {
+                assert_eq!(sq.y(), r);
+            }
+        }
+
+        assert_eq!(Bitboard::rank(0).bits, 0xff);
+        assert_eq!(Bitboard::rank(7).bits, 0xff00_0000_0000_0000);
+    }
+
+    #[test]
+    fn file_masks() {
+        for f in 0..8 {
+            let file = Bitboard::file(f);
+            assert_eq!(file, Bitboard::FILES[f as usize]);
+            assert_eq!(file.count(), 8);
+
+            for sq in file {
+                assert_eq!(sq.x(), f);
+            }
+        }
+
+        let indices: Vec<u8> = Bitboard::file(0).into_iter().map(|sq| sq.index()).collect();
+        assert_eq!(indices, [0, 8, 16, 24, 32, 40, 48, 56]);
+    }
+
+    #[test]
+    fn rank_and_file_masks_partition() {
+        let ranks = Bitboard::RANKS
+            .iter()
+            .fold(Bitboard::EMPTY, |acc, &bb| acc | bb);
+        let files = Bitboard::FILES
+            .iter()
+            .fold(Bitboard::EMPTY, |acc, &bb| acc | bb);
+        assert_eq!(ranks, Bitboard::FULL);
+        assert_eq!(files, Bitboard::FULL);
+
+        for r in 0..8 {
+            for f in 0..8 {
+                let cross = Bitboard::rank(r) & Bitboard::file(f);
+                assert_eq!(cross, squares(&[&Square::new(f, r).unwrap().to_string()]));
+            }
+        }
+    }
+
+    #[test]
+    fn display_plain() {
+        let expected = "0 0 0 0 0 0 0 1\n\
+                        0 0 0 0 0 0 0 0\n\
+                        0 0 0 0 0 0 0 0\n\
+                        0 0 0 0 0 0 0 0\n\
+                        0 0 0 0 0 0 0 0\n\
+                        0 0 0 0 0 0 0 0\n\
+                        0 1 0 0 0 0 0 0\n\
+                        1 0 0 0 0 0 0 0";
+        assert_eq!(squares(&["a1", "b2", "h8"]).to_string(), expected);
+    }
+
+    #[test]
+    fn display_alternate() {
+        let expected = "8 0 0 0 0 0 0 0 1\n\
+                        7 0 0 0 0 0 0 0 0\n\
+                        6 0 0 0 0 0 0 0 0\n\
+                        5 0 0 0 0 0 0 0 0\n\
+                        4 0 0 0 0 0 0 0 0\n\
+                        3 0 0 0 0 0 0 0 0\n\
+                        2 0 1 0 0 0 0 0 0\n\
+                        1 1 0 0 0 0 0 0 0\n  \
+                        a b c d e f g h";
+        assert_eq!(format!("{:#}", squares(&["a1", "b2", "h8"])), expected);
+    }
+
+    #[test]
+    fn display_a8_top_left() {
+        let display = squares(&["a8"]).to_string();
+        let mut lines = display.lines();
+        assert_eq!(lines.next(), Some("1 0 0 0 0 0 0 0"));
+        assert!(lines.all(|line| line == "0 0 0 0 0 0 0 0"));
+    }
+
+    #[test]
+    fn flip_vertical() {
+        assert_eq!(squares(&["a1"]).flip_vertical(), squares(&["a8"]));
+        assert_eq!(
+            squares(&["e2", "c6"]).flip_vertical(),
+            squares(&["e7", "c3"])
+        );
+        assert_eq!(Bitboard::rank(1).flip_vertical(), Bitboard::rank(6));
+        assert_eq!(Bitboard::file(3).flip_vertical(), Bitboard::file(3));
+    }
+
+    #[test]
+    fn mirror_horizontal() {
+        assert_eq!(squares(&["a1"]).mirror_horizontal(), squares(&["h1"]));
+        assert_eq!(
+            squares(&["b3", "f8"]).mirror_horizontal(),
+            squares(&["g3", "c8"])
+        );
+        assert_eq!(Bitboard::file(0).mirror_horizontal(), Bitboard::file(7));
+        assert_eq!(Bitboard::rank(4).mirror_horizontal(), Bitboard::rank(4));
+    }
+
+    #[test]
+    fn reflections_are_involutions() {
+        for bits in [0, u64::MAX, 1, 0x0123_4567_89ab_cdef, 0x8040_2010_0804_0201] {
+            let bb = Bitboard::from(bits);
+            assert_eq!(bb.flip_vertical().flip_vertical(), bb);
+            assert_eq!(bb.mirror_horizontal().mirror_horizontal(), bb);
+        }
+    }
+
+    #[test]
+    fn knight_attacks_corner() {
+        let a1 = Square::from_string("a1").unwrap();
+        assert_eq!(bitboard::knight_attacks(a1), squares(&["b3", "c2"]));
+
+        let h8 = Square::from_string("h8").unwrap();
+        assert_eq!(bitboard::knight_attacks(h8), squares(&["g6", "f7"]));
+    }
+
+    #[test]
+    fn knight_attacks_edge() {
+        let a4 = Square::from_string("a4").unwrap();
+        assert_eq!(
+            bitboard::knight_attacks(a4),
+            squares(&["b6", "c5", "c3", "b2"])
+        );
+
+        let g1 = Square::from_string("g1").unwrap();
+        assert_eq!(bitboard::knight_attacks(g1), squares(&["e2", "f3", "h3"]));
+    }
+
+    #[test]
+    fn knight_attacks_center() {
+        let d4 = Square::from_string("d4").unwrap();
+        let expected = squares(&["c6", "e6", "f5", "f3", "e2", "c2", "b3", "b5"]);
+        assert_eq!(bitboard::knight_attacks(d4), expected);
+    }
+
+    #[test]
+    fn king_attacks_center() {
+        let e4 = Square::from_string("e4").unwrap();
+        let expected = squares(&["d3", "e3", "f3", "d4", "f4", "d5", "e5", "f5"]);
+        assert_eq!(bitboard::king_attacks(e4), expected);
+
+        for x in 1..7 {
+            for y in 1..7 {
+                assert_eq!(
+                    bitboard::king_attacks(Square::new(x, y).unwrap()).count(),
+                    8
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn king_attacks_edge() {
+        let e1 = Square::from_string("e1").unwrap();
+        assert_eq!(
+            bitboard::king_attacks(e1),
+            squares(&["d1", "f1", "d2", "e2", "f2"])
+        );
+
+        for i in 1..7 {
+            for sq in [(i, 0), (i, 7), (0, i), (7, i)] {
+                let sq = Square::new(sq.0, sq.1).unwrap();
+                assert_eq!(bitboard::king_attacks(sq).count(), 5);
+            }
+        }
+    }
+
+    #[test]
+    fn king_attacks_corner() {
+        let h8 = Square::from_string("h8").unwrap();
+        assert_eq!(bitboard::king_attacks(h8), squares(&["g8", "g7", "h7"]));
+
+        for name in ["a1", "h1", "a8", "h8"] {
+            let sq = Square::from_string(name).unwrap();
+            assert_eq!(bitboard::king_attacks(sq).count(), 3);
+        }
+    }
+
+    #[test]
+    fn pawn_attacks_center() {
+        let e4 = Square::from_string("e4").unwrap();
+        assert_eq!(
+            bitboard::pawn_attacks(e4, Color::White),
+            squares(&["d5", "f5"])
+        );
+        assert_eq!(
+            bitboard::pawn_attacks(e4, Color::Black),
+            squares(&["d3", "f3"])
+        );
+    }
+
+    #[test]
+    fn pawn_attacks_file_edges() {
+        let a2 = Square::from_string("a2").unwrap();
+        assert_eq!(bitboard::pawn_attacks(a2, Color::White), squares(&["b3"]));
+        assert_eq!(bitboard::pawn_attacks(a2, Color::Black), squares(&["b1"]));
+
+        let h7 = Square::from_string("h7").unwrap();
+        assert_eq!(bitboard::pawn_attacks(h7, Color::White), squares(&["g8"]));
+        assert_eq!(bitboard::pawn_attacks(h7, Color::Black), squares(&["g6"]));
+    }
+
+    #[test]
+    fn pawn_attacks_last_rank() {
+        let d8 = Square::from_string("d8").unwrap();
+        assert!(bitboard::pawn_attacks(d8, Color::White).none());
+        assert_eq!(
+            bitboard::pawn_attacks(d8, Color::Black),
+            squares(&["c7", "e7"])
+        );
+
+        let d1 = Square::from_string("d1").unwrap();
+        assert!(bitboard::pawn_attacks(d1, Color::Black).none());
+        assert_eq!(
+            bitboard::pawn_attacks(d1, Color::White),
+            squares(&["c2", "e2"])
+        );
+    }
+
+    #[test]
+    fn from_square() {
+        let e4 = Square::from_string("e4").unwrap();
+        assert_eq!(Bitboard::from(e4), squares(&["e4"]));
+    }
+
+    #[test]
+    fn rook_attacks_open_board() {
+        for sq in Bitboard::FULL {
+            let attacks = bitboard::rook_attacks(sq, Bitboard::EMPTY);
+            let expected = (Bitboard::rank(sq.y()) | Bitboard::file(sq.x())) ^ Bitboard::from(sq);
+            assert_eq!(attacks, expected);
+            assert_eq!(attacks.count(), 14);
+        }
+    }
+
+    #[test]
+    fn rook_attacks_boxed_in() {
+        let d4 = Square::from_string("d4").unwrap();
+        let neighbors = squares(&["d5", "d3", "c4", "e4"]);
+        let occupied = neighbors | squares(&["d4", "d8", "a4", "h4", "d1"]);
+        assert_eq!(bitboard::rook_attacks(d4, occupied), neighbors);
+    }
+
+    #[test]
+    fn rook_attacks_blockers() {
+        let a1 = Square::from_string("a1").unwrap();
+        let occupied = squares(&["a3", "a5", "d1"]);
+        let expected = squares(&["a2", "a3", "b1", "c1", "d1"]);
+        assert_eq!(bitboard::rook_attacks(a1, occupied), expected);
+    }
+
+    #[test]
+    fn bishop_attacks_corner() {
+        let a1 = Square::from_string("a1").unwrap();
+        let expected = squares(&["b2", "c3", "d4", "e5", "f6", "g7", "h8"]);
+        assert_eq!(bitboard::bishop_attacks(a1, Bitboard::EMPTY), expected);
+    }
+
+    #[test]
+    fn bishop_attacks_open_board() {
+        let d4 = Square::from_string("d4").unwrap();
+        let expected = squares(&[
+            "a1", "b2", "c3", "e5", "f6", "g7", "h8", "a7", "b6", "c5", "e3", "f2", "g1",
+        ]);
+        assert_eq!(bitboard::bishop_attacks(d4, Bitboard::EMPTY), expected);
+    }
+
+    #[test]
+    fn bishop_attacks_blocker() {
+        let a1 = Square::from_string("a1").unwrap();
+        let occupied = squares(&["d4", "f6"]);
+        assert_eq!(
+            bitboard::bishop_attacks(a1, occupied),
+            squares(&["b2", "c3", "d4"])
+        );
+    }
+
+    #[test]
+    fn bishop_attacks_no_wrap() {
+        let h4 = Square::from_string("h4").unwrap();
+        let expected = squares(&["g5", "f6", "e7", "d8", "g3", "f2", "e1"]);
+        assert_eq!(bitboard::bishop_attacks(h4, Bitboard::EMPTY), expected);
+    }
+
+    #[test]
+    fn queen_attacks_open_board() {
+        let d4 = Square::from_string("d4").unwrap();
+        let rook = bitboard::rook_attacks(d4, Bitboard::EMPTY);
+        let bishop = bitboard::bishop_attacks(d4, Bitboard::EMPTY);
+        let queen = bitboard::queen_attacks(d4, Bitboard::EMPTY);
+
+        assert!((rook & bishop).none());
+        assert_eq!(queen, rook | bishop);
+        assert_eq!(queen.count(), rook.count() + bishop.count());
+        assert_eq!(queen.count(), 27);
+    }
+
+    #[test]
+    fn queen_attacks_blockers() {
+        let a1 = Square::from_string("a1").unwrap();
+        let occupied = squares(&["a2", "b1", "b2"]);
+        assert_eq!(bitboard::queen_attacks(a1, occupied), occupied);
+    }
+
+    #[test]
+    fn subsets_three_bits() {
+        let mask = squares(&["a1", "e4", "h8"]);
+        let subsets: HashSet<Bitboard> = mask.subsets().collect();
+        assert_eq!(mask.subsets().count(), 8);
+        assert_eq!(subsets.len(), 8);
+
+        for subset in &subsets {
+            assert_eq!(*subset & mask, *subset);
+        }
+
+        assert_eq!(mask.subsets().next(), Some(Bitboard::EMPTY));
+        assert_eq!(mask.subsets().last(), Some(mask));
+    }
+
+    #[test]
+    fn subsets_empty() {
+        let subsets: Vec<Bitboard> = Bitboard::EMPTY.subsets().collect();
+        assert_eq!(subsets, [Bitboard::EMPTY]);
+    }
+
+    #[test]
+    fn subsets_rank() {
+        assert_eq!(Bitboard::rank(3).subsets().count(), 256);
+    }
+
+    #[test]
+    fn between_collinear() {
+        let e1 = Square::from_string("e1").unwrap();
+        let e4 = Square::from_string("e4").unwrap();
+        assert_eq!(bitboard::between(e1, e4), squares(&["e2", "e3"]));
+        assert_eq!(bitboard::between(e4, e1), squares(&["e2", "e3"]));
+
+        let a5 = Square::from_string("a5").unwrap();
+        let h5 = Square::from_string("h5").unwrap();
+        let expected = squares(&["b5", "c5", "d5", "e5", "f5", "g5"]);
+        assert_eq!(bitboard::between(a5, h5), expected);
+    }
+
+    #[test]
+    fn between_diagonal() {
+        let a1 = Square::from_string("a1").unwrap();
+        let h8 = Square::from_string("h8").unwrap();
+        let expected = squares(&["b2", "c3", "d4", "e5", "f6", "g7"]);
+        assert_eq!(bitboard::between(a1, h8), expected);
+
+        let b6 = Square::from_string("b6").unwrap();
+        let e3 = Square::from_string("e3").unwrap();
+        assert_eq!(bitboard::between(b6, e3), squares(&["c5", "d4"]));
+    }
+
+    #[test]
+    fn between_adjacent_and_same() {
+        let d4 = Square::from_string("d4").unwrap();
+        let e5 = Square::from_string("e5").unwrap();
+        assert!(bitboard::between(d4, e5).none());
+        assert!(bitboard::between(d4, d4).none());
+    }
+
+    #[test]
+    fn between_not_aligned() {
+        let a1 = Square::from_string("a1").unwrap();
+        let b3 = Square::from_string("b3").unwrap();
+        let h7 = Square::from_string("h7").unwrap();
+        assert!(bitboard::between(a1, b3).none());
+        assert!(bitboard::between(a1, h7).none());
+    }
+
+    #[test]
+    fn ray_examples() {
+        let e4 = Square::from_string("e4").unwrap();
+        assert_eq!(
+            bitboard::ray(e4, Direction::North),
+            squares(&["e5", "e6", "e7", "e8"])
+        );
+        assert_eq!(
+            bitboard::ray(e4, Direction::NorthEast),
+            squares(&["f5", "g6", "h7"])
+        );
+        assert_eq!(
+            bitboard::ray(e4, Direction::West),
+            squares(&["d4", "c4", "b4", "a4"])
+        );
+        assert_eq!(
+            bitboard::ray(e4, Direction::SouthWest),
+            squares(&["d3", "c2", "b1"])
+        );
+    }
+
+    #[test]
+    fn ray_off_board() {
+        for sq in Bitboard::rank(7) {
+            assert!(bitboard::ray(sq, Direction::North).none());
+            assert!(bitboard::ray(sq, Direction::NorthEast).none());
+            assert!(bitboard::ray(sq, Direction::NorthWest).none());
+        }
+
+        let a1 = Square::from_string("a1").unwrap();
+        assert!(bitboard::ray(a1, Direction::West).none());
+        assert!(bitboard::ray(a1, Direction::South).none());
+        assert!(bitboard::ray(a1, Direction::SouthEast).none());
+    }
+
+    #[test]
+    fn rays_cover_queen_moves() {
+        for sq in Bitboard::FULL {
+            let rays = Direction::ALL
+                .into_iter()
+                .fold(Bitboard::EMPTY, |acc, dir| acc | bitboard::ray(sq, dir));
+            assert_eq!(rays, bitboard::queen_attacks(sq, Bitboard::EMPTY));
+        }
+    }
+
+    #[test]
+    fn direction_opposite() {
+        for dir in Direction::ALL {
+            assert_ne!(dir.opposite(), dir);
+            assert_eq!(dir.opposite().opposite(), dir);
+            assert_ne!(dir.is_increasing(), dir.opposite().is_increasing());
+        }
+        assert_eq!(Direction::North.opposite(), Direction::South);
+        assert_eq!(Direction::NorthEast.opposite(), Direction::SouthWest);
+    }
+
+    #[test]
+    fn from_fen_rank() {
+        assert_eq!(
+            Bitboard::from_fen_rank(0, "p1p1p1p1"),
+            Ok(squares(&["a1", "c1", "e1", "g1"]))
+        );
+        assert_eq!(
+            Bitboard::from_fen_rank(7, "r3k2r"),
+            Ok(squares(&["a8", "e8", "h8"]))
+        );
+        assert_eq!(Bitboard::from_fen_rank(3, "8"), Ok(Bitboard::EMPTY));
+    }
+
+    #[test]
+    fn from_fen_rank_invalid() {
+        assert_eq!(
+            Bitboard::from_fen_rank(0, "ppp"),
+            Err(FenError::WrongSquareCount)
+        );
+        assert_eq!(
+            Bitboard::from_fen_rank(0, "pppppppp1"),
+            Err(FenError::WrongSquareCount)
+        );
+        assert_eq!(
+            Bitboard::from_fen_rank(7, "ppppppppp"),
+            Err(FenError::WrongSquareCount)
+        );
+        assert_eq!(
+            Bitboard::from_fen_rank(0, "44p"),
+            Err(FenError::WrongSquareCount)
+        );
+        assert_eq!(
+            Bitboard::from_fen_rank(0, "p0ppppppp"),
+            Err(FenError::InvalidPiece)
+        );
+        assert_eq!(
+            Bitboard::from_fen_rank(8, "pppppppp"),
+            Err(FenError::RankOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn from_fen_rank_matches_board() {
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R";
+        let board = Board::from_fen(fen).unwrap();
+        for (rank, field) in (0..8).rev().zip(fen.split('/')) {
+            let expected = board.occupied() & Bitboard::rank(rank);
+            assert_eq!(Bitboard::from_fen_rank(rank, field), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn fold() {
+        let bb = squares(&["a1", "e4", "h8", "c7"]);
+        let mut expected = 0;
+        for sq in bb {
+            expected += sq.index() as u32;
+        }
+        assert_eq!(bb.fold(0, |sum, sq| sum + sq.index() as u32), expected);
+        assert_eq!(Bitboard::EMPTY.fold(5, |sum, sq| sum + sq.index()), 5);
+    }
+
+    #[test]
+    fn light_and_dark_squares() {
+        assert_eq!(
+            Bitboard::LIGHT_SQUARES ^ Bitboard::DARK_SQUARES,
+            Bitboard::FULL
+        );
+        assert_eq!(
+            Bitboard::LIGHT_SQUARES & Bitboard::DARK_SQUARES,
+            Bitboard::EMPTY
+        );
+        assert_eq!(Bitboard::LIGHT_SQUARES.count(), 32);
+        assert!(Bitboard::DARK_SQUARES.get(Square::from_string("a1").unwrap()));
+
+        for sq in Square::all() {
+            let dark = sq.color() == SquareColor::Dark;
+            assert_eq!(Bitboard::DARK_SQUARES.get(sq), dark);
+            assert_eq!(Bitboard::LIGHT_SQUARES.get(sq), !dark);
+        }
+    }
+
+    #[test]
+    fn nearest_distance() {
+        let pawns = squares(&["a7", "f5", "h2"]);
+        for (name, distance) in [("e4", 1), ("b1", 4), ("a8", 1), ("f5", 0)] {
+            let sq = Square::from_string(name).unwrap();
+            assert_eq!(pawns.nearest_distance(sq), Some(distance));
+            assert_eq!(Bitboard::EMPTY.nearest_distance(sq), None);
+        }
+    }
+
+    #[test]
+    fn iter_bits() {
+        let bb = squares(&["a1", "d4", "h8"]);
+        let pairs: Vec<_> = bb.iter_bits().collect();
+        assert_eq!(pairs.len(), 3);
+
+        for (sq, bit) in &pairs {
+            assert_eq!(Bitboard::from(*sq), *bit);
+        }
+
+        let squares: Vec<Square> = pairs.iter().map(|&(sq, _)| sq).collect();
+        assert_eq!(squares, bb.into_iter().collect::<Vec<_>>());
+        assert_eq!(
+            pairs
+                .iter()
+                .fold(Bitboard::EMPTY, |acc, &(_, bit)| acc | bit),
+            bb
+        );
+        assert_eq!(Bitboard::EMPTY.iter_bits().count(), 0);
+    }
+
+    #[test]
+    fn forward() {
+        let pawns = squares(&["a2", "e4", "h7"]);
+        assert_eq!(pawns.forward(Color::White), squares(&["a3", "e5", "h8"]));
+        assert_eq!(pawns.forward(Color::Black), squares(&["a1", "e3", "h6"]));
+        assert!(Bitboard::rank(7).forward(Color::White).none());
+        assert!(Bitboard::rank(0).forward(Color::Black).none());
+    }
+
+    #[test]
+    fn forward_diagonals() {
+        let pawns = squares(&["a2", "e4", "h7"]);
+        assert_eq!(pawns.forward_left(Color::White), squares(&["d5", "g8"]));
+        assert_eq!(pawns.forward_right(Color::White), squares(&["b3", "f5"]));
+        assert_eq!(pawns.forward_left(Color::Black), squares(&["d3", "g6"]));
+        assert_eq!(pawns.forward_right(Color::Black), squares(&["b1", "f3"]));
+    }
+
+    #[test]
+    fn pretty_with() {
+        let knight = squares(&["g1"]);
+        let attacks = bitboard::knight_attacks(Square::from_string("g1").unwrap());
+        let pretty = knight.pretty_with(attacks);
+        let lines: Vec<&str> = pretty.lines().collect();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[5], "3 . . . . . X . X");
+        assert_eq!(lines[6], "2 . . . . X . . .");
+        assert_eq!(lines[7], "1 . . . . . . * .");
+        assert_eq!(lines[8], "  a b c d e f g h");
+        assert_eq!(pretty.matches('X').count(), 3);
+        assert_eq!(pretty.matches('*').count(), 1);
+    }
+
+    #[test]
+    fn pretty_with_matches_labelled_display() {
+        let bb = squares(&["a1", "d4", "h8"]);
+        let pretty = bb.pretty_with(Bitboard::EMPTY);
+        assert_eq!(
+            pretty.replace('*', "1").replace('.', "0"),
+            format!("{:#}", bb)
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use sachy::bitboard;
+    use sachy::square::Square;
+    use sachy::Bitboard;
+
+    #[test]
+    fn square_round_trip() {
+        for sq in Square::all() {
+            let json = serde_json::to_string(&sq).unwrap();
+            assert_eq!(json, format!("\"{}\"", sq));
+            assert_eq!(serde_json::from_str::<Square>(&json).unwrap(), sq);
+        }
+    }
+
+    #[test]
+    fn square_invalid() {
+        assert!(serde_json::from_str::<Square>("\"i9\"").is_err());
+        assert!(serde_json::from_str::<Square>("\"e44\"").is_err());
+        assert!(serde_json::from_str::<Square>("28").is_err());
+    }
+
+    #[test]
+    fn bitboard_round_trip() {
+        let e4 = Square::from_string("e4").unwrap();
+        let boards = [
+            Bitboard::EMPTY,
+            Bitboard::FULL,
+            Bitboard::from(e4),
+            bitboard::knight_attacks(e4),
+            bitboard::queen_attacks(e4, Bitboard::rank(5)),
+        ];
+
+        for bb in boards {
+            let json = serde_json::to_string(&bb).unwrap();
+            assert_eq!(json, bb.bits.to_string());
+            assert_eq!(serde_json::from_str::<Bitboard>(&json).unwrap(), bb);
+        }
+    }
+
+    #[test]
+    fn bitboard_invalid() {
+        assert!(serde_json::from_str::<Bitboard>("-1").is_err());
+        assert!(serde_json::from_str::<Bitboard>("\"e4\"").is_err());
+    }
+}